@@ -19,6 +19,7 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::must_use_candidate)]
 #![allow(clippy::similar_names)]
+#![allow(clippy::doc_overindented_list_items)]
 
 //! This module provides Rust bindings for libvoikko.
 //!
@@ -35,8 +36,12 @@ mod tests;
 pub mod voikko {
 
     use crate::libvoikko;
-    use std::collections::HashMap;
+    pub use crate::libvoikko::VoikkoHandle;
+    use std::borrow::Cow;
+    use std::cell::{Cell, RefCell};
+    use std::collections::{HashMap, HashSet, VecDeque};
     use std::error;
+    use std::io::{BufRead, Read, Write};
     use unicode_segmentation::UnicodeSegmentation;
 
     /// Returns the version number of libvoikko.
@@ -44,6 +49,28 @@ pub mod voikko {
         libvoikko::version()
     }
 
+    /// Information about the linked libvoikko, returned by [`backend_info()`]
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct BackendInfo {
+        /// Version number of libvoikko
+        pub version: String,
+        /// Whether grammar checking is supported for at least one language
+        pub has_grammar_checker: bool,
+        /// Number of dictionaries found in the standard locations
+        pub dictionary_count: usize,
+    }
+
+    /// Returns information about the linked libvoikko and its dictionaries in the standard
+    /// locations, e.g. for bug reports.
+    #[must_use]
+    pub fn backend_info() -> BackendInfo {
+        BackendInfo {
+            version: String::from(version()),
+            has_grammar_checker: !list_supported_grammar_checking_languages("").is_empty(),
+            dictionary_count: list_dicts("").len(),
+        }
+    }
+
     /// Information about an available dictionary
     ///
     /// Contains the language, script, variant and human readable description
@@ -75,13 +102,361 @@ pub mod voikko {
                 description: String::from(description),
             }
         }
+
+        /// Returns the BCP 47 language tag that selects this dictionary, e.g. `"fi-x-morphoid"`.
+        /// Empty script and variant are left out. This is the inverse of `parse_language_tag()`.
+        #[must_use]
+        pub fn language_tag(&self) -> String {
+            let mut tag = self.language.clone();
+            if !self.script.is_empty() {
+                tag.push('-');
+                tag.push_str(&self.script);
+            }
+            if !self.variant.is_empty() {
+                tag.push_str("-x-");
+                tag.push_str(&self.variant);
+            }
+            tag
+        }
+    }
+
+    /// Split a BCP 47 language tag into its language, script and private use variant, for example
+    /// `"fi-x-morphoid"` into `("fi", "", "morphoid")`. Parts missing from the tag are returned as
+    /// empty strings, and other subtags such as regions are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - language tag to parse
+    #[must_use]
+    pub fn parse_language_tag(tag: &str) -> (String, String, String) {
+        let (main, variant) = match tag.find("-x-") {
+            Some(i) => (&tag[..i], &tag[i + 3..]),
+            None => (tag, ""),
+        };
+        let mut subtags = main.split('-');
+        let language = subtags.next().unwrap_or_default();
+        let script = subtags
+            .find(|subtag| subtag.len() == 4 && subtag.chars().all(|c| c.is_ascii_alphabetic()))
+            .unwrap_or_default();
+        (String::from(language), String::from(script), String::from(variant))
     }
 
     /// A morphological analysis item
-    pub type Analysis = HashMap<String, String>;
+    ///
+    /// Maps attribute names such as `"BASEFORM"` to their values. The map can be used directly
+    /// through `Deref`, and the most common attributes also have accessors.
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct Analysis(HashMap<String, String>);
+
+    impl Analysis {
+        /// Construct new empty Analysis.
+        #[must_use]
+        pub fn new() -> Analysis {
+            Analysis(HashMap::new())
+        }
+
+        /// Value of given attribute, or `None` if the analysis does not have it.
+        ///
+        /// # Arguments
+        ///
+        /// * `key` - attribute name, e.g. `"SIJAMUOTO"`
+        #[must_use]
+        pub fn get(&self, key: &str) -> Option<&str> {
+            self.0.get(key).map(String::as_str)
+        }
+
+        /// Base form of the word from the `BASEFORM` attribute.
+        #[must_use]
+        pub fn baseform(&self) -> Option<&str> {
+            self.get("BASEFORM")
+        }
+
+        /// Word class from the `CLASS` attribute, e.g. `"nimisana"`.
+        #[must_use]
+        pub fn word_class(&self) -> Option<&str> {
+            self.get("CLASS")
+        }
+
+        /// Consumes the analysis and returns the underlying map.
+        #[must_use]
+        pub fn into_inner(self) -> HashMap<String, String> {
+            self.0
+        }
+    }
+
+    impl std::ops::Deref for Analysis {
+        type Target = HashMap<String, String>;
+
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    impl std::ops::DerefMut for Analysis {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            &mut self.0
+        }
+    }
+
+    impl From<HashMap<String, String>> for Analysis {
+        fn from(map: HashMap<String, String>) -> Self {
+            Analysis(map)
+        }
+    }
+
+    /// Analysis attributes compared by `analysis_semantic_eq()`. Unlike e.g. `FSTOUTPUT` and
+    /// `WORDIDS`, these do not contain dictionary-specific word IDs.
+    pub const STABLE_ANALYSIS_KEYS: &[&str] = &["CLASS", "BASEFORM", "SIJAMUOTO", "NUMBER", "STRUCTURE"];
+
+    /// Compare two analyses by the attributes in `STABLE_ANALYSIS_KEYS` only, so that analyses
+    /// from different libvoikko or dictionary versions can be compared.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - first analysis
+    /// * `b` - second analysis
+    #[must_use]
+    pub fn analysis_semantic_eq(a: &Analysis, b: &Analysis) -> bool {
+        analysis_eq_by_keys(a, b, STABLE_ANALYSIS_KEYS)
+    }
+
+    /// Compare two analyses by the given attributes only. An attribute missing from both analyses
+    /// is considered equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - first analysis
+    /// * `b` - second analysis
+    /// * `keys` - attributes to compare
+    #[must_use]
+    pub fn analysis_eq_by_keys(a: &Analysis, b: &Analysis, keys: &[&str]) -> bool {
+        keys.iter().all(|&key| a.get(key) == b.get(key))
+    }
+
+    /// Grammatical number, parsed from the `NUMBER` attribute of an [`Analysis`]
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub enum Number {
+        /// Singular (`"singular"`)
+        Singular,
+        /// Plural (`"plural"`)
+        Plural,
+        /// A value not recognized by this crate
+        Unknown(String),
+    }
+
+    impl From<&str> for Number {
+        fn from(value: &str) -> Self {
+            match value {
+                "singular" => Number::Singular,
+                "plural" => Number::Plural,
+                other => Number::Unknown(String::from(other)),
+            }
+        }
+    }
+
+    /// Word class, parsed from the `CLASS` attribute of an [`Analysis`]
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub enum WordClass {
+        /// Noun (`"nimisana"`)
+        Noun,
+        /// Adjective (`"laatusana"`)
+        Adjective,
+        /// Noun or adjective (`"nimisana_laatusana"`)
+        NounAdjective,
+        /// Verb (`"teonsana"`)
+        Verb,
+        /// Adverb (`"seikkasana"`)
+        Adverb,
+        /// Pronoun (`"asemosana"`)
+        Pronoun,
+        /// Adposition (`"suhdesana"`)
+        Adposition,
+        /// Interjection (`"huudahdussana"`)
+        Interjection,
+        /// Conjunction (`"sidesana"`)
+        Conjunction,
+        /// First name (`"etunimi"`)
+        FirstName,
+        /// Last name (`"sukunimi"`)
+        LastName,
+        /// Place name (`"paikannimi"`)
+        PlaceName,
+        /// Other proper noun (`"nimi"`)
+        ProperNoun,
+        /// Negative verb (`"kieltosana"`)
+        NegativeVerb,
+        /// Abbreviation (`"lyhenne"`)
+        Abbreviation,
+        /// Numeral (`"lukusana"`)
+        Numeral,
+        /// Prefix (`"etuliite"`)
+        Prefix,
+        /// A value not recognized by this crate
+        Unknown(String),
+    }
+
+    impl From<&str> for WordClass {
+        fn from(value: &str) -> Self {
+            match value {
+                "nimisana" => WordClass::Noun,
+                "laatusana" => WordClass::Adjective,
+                "nimisana_laatusana" => WordClass::NounAdjective,
+                "teonsana" => WordClass::Verb,
+                "seikkasana" => WordClass::Adverb,
+                "asemosana" => WordClass::Pronoun,
+                "suhdesana" => WordClass::Adposition,
+                "huudahdussana" => WordClass::Interjection,
+                "sidesana" => WordClass::Conjunction,
+                "etunimi" => WordClass::FirstName,
+                "sukunimi" => WordClass::LastName,
+                "paikannimi" => WordClass::PlaceName,
+                "nimi" => WordClass::ProperNoun,
+                "kieltosana" => WordClass::NegativeVerb,
+                "lyhenne" => WordClass::Abbreviation,
+                "lukusana" => WordClass::Numeral,
+                "etuliite" => WordClass::Prefix,
+                other => WordClass::Unknown(String::from(other)),
+            }
+        }
+    }
+
+    /// Typed view of a morphological analysis
+    ///
+    /// Wraps an [`Analysis`] and provides typed accessors for its attributes.
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct MorphologyInfo {
+        analysis: Analysis,
+    }
+
+    impl MorphologyInfo {
+        /// Construct new `MorphologyInfo` from an analysis.
+        #[must_use]
+        pub fn new(analysis: Analysis) -> MorphologyInfo {
+            MorphologyInfo { analysis }
+        }
+
+        /// The underlying analysis.
+        pub fn analysis(&self) -> &Analysis {
+            &self.analysis
+        }
+
+        /// Grammatical number of the word, or `None` if the analysis has no `NUMBER` attribute.
+        pub fn number(&self) -> Option<Number> {
+            self.analysis.get("NUMBER").map(Number::from)
+        }
+
+        /// Weight of the analysis from the `WEIGHT` attribute. Can be used for ranking ambiguous
+        /// analyses. Returns `None` if the dictionary does not provide weights or the value is
+        /// not a number.
+        pub fn weight(&self) -> Option<f64> {
+            self.analysis.get("WEIGHT").and_then(|value| value.parse().ok())
+        }
+
+        /// Dictionary word IDs of the parts of the word, parsed from the `WORDIDS` attribute.
+        /// Returns an empty vector if the analysis has no `WORDIDS` attribute.
+        pub fn word_ids(&self) -> Vec<WordId> {
+            self.analysis.get("WORDIDS").map_or_else(Vec::new, parse_wordids)
+        }
+    }
+
+    impl From<Analysis> for MorphologyInfo {
+        fn from(analysis: Analysis) -> Self {
+            MorphologyInfo::new(analysis)
+        }
+    }
+
+    /// A symbol in the `FSTOUTPUT` attribute of an [`Analysis`]
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct FstSymbol {
+        /// Tag without the surrounding brackets, e.g. `"Ln"` for `[Ln]`. Empty for literal text
+        /// at the start of the output.
+        pub tag: String,
+        /// Literal text following the tag, if any
+        pub value: Option<String>,
+    }
+
+    /// Parse the `FSTOUTPUT` attribute of an analysis into a sequence of symbols.
+    ///
+    /// The output consists of bracketed tags such as `[Ln]` and `[Xp]`, each optionally followed
+    /// by literal text that becomes the `value` of its symbol.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - value of the `FSTOUTPUT` attribute
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use voikko_rs::voikko::{parse_fstoutput, FstSymbol};
+    ///
+    /// let symbols = parse_fstoutput("[Ln][Xp]kissa[X]kiss[Sn][Ny]a");
+    /// assert_eq!(symbols[1], FstSymbol { tag: "Xp".to_string(), value: Some("kissa".to_string()) });
+    /// ```
+    pub fn parse_fstoutput(s: &str) -> Vec<FstSymbol> {
+        let mut symbols = Vec::new();
+        let mut rest = s;
+        while !rest.is_empty() {
+            let tag = match rest.strip_prefix('[').and_then(|r| r.split_once(']')) {
+                Some((tag, after_tag)) => {
+                    rest = after_tag;
+                    Some(tag)
+                }
+                None => None,
+            };
+            // the value runs until the next tag. without a tag, the text is taken literally even
+            // if it starts with an unterminated bracket.
+            let value_len = match tag {
+                Some(_) => rest.find('['),
+                None => rest.char_indices().skip(1).find(|&(_, c)| c == '[').map(|(i, _)| i),
+            }
+            .unwrap_or(rest.len());
+            let value = &rest[..value_len];
+            symbols.push(FstSymbol {
+                tag: String::from(tag.unwrap_or_default()),
+                value: if value.is_empty() { None } else { Some(String::from(value)) },
+            });
+            rest = &rest[value_len..];
+        }
+        symbols
+    }
+
+    /// A part of a word in the `WORDIDS` attribute of an [`Analysis`]
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct WordId {
+        /// Text of the word part, e.g. `"kalja"`
+        pub surface: String,
+        /// Dictionary word ID of the part, e.g. `"w504403"`. Empty if the part has no ID.
+        pub id: String,
+    }
+
+    /// Parse the `WORDIDS` attribute of an analysis into its parts.
+    ///
+    /// Each part of a compound word starts with `+` and is followed by its word ID in
+    /// parentheses, e.g. `"+kalja(w504403)+kori(w506023)"`.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - value of the `WORDIDS` attribute
+    pub fn parse_wordids(s: &str) -> Vec<WordId> {
+        s.split('+')
+            .filter(|part| !part.is_empty())
+            .map(|part| match part.split_once('(') {
+                Some((surface, rest)) => WordId {
+                    surface: String::from(surface),
+                    id: String::from(rest.split_once(')').map_or(rest, |(id, _)| id)),
+                },
+                None => WordId {
+                    surface: String::from(part),
+                    id: String::new(),
+                },
+            })
+            .collect()
+    }
 
     /// Get a list of available dictionaries. Returns a vector of Dictionary structs.
     ///
+    /// Invalid UTF-8 in dictionary information is replaced with U+FFFD REPLACEMENT CHARACTER.
+    ///
     /// # Arguments
     ///
     /// * `path` - Path to a directory from which dictionary files should be searched
@@ -91,6 +466,98 @@ pub mod voikko {
         libvoikko::list_dicts(path).unwrap_or_else(|_| vec![])
     }
 
+    /// Get a list of available dictionaries as `(language_tag, description)` pairs, in the order
+    /// of `list_dicts()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to a directory from which dictionary files should be searched
+    ///            first before looking into the standard dictionary locations.
+    ///            Pass an empty string in order to only look in standard locations.
+    #[must_use]
+    pub fn dictionary_summaries(path: &str) -> Vec<(String, String)> {
+        list_dicts(path)
+            .into_iter()
+            .map(|dict| (dict.language_tag(), dict.description))
+            .collect()
+    }
+
+    /// Find the dictionary that `Voikko::new()` would use for given language tag.
+    ///
+    /// libvoikko lists dictionaries in order of preference, so this is the first dictionary
+    /// from `list_dicts()` that matches the language, and the script and variant if the tag
+    /// specifies them. Returns `None` if no dictionary matches.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - BCP 47 language tag, e.g. `"fi"` or `"fi-x-morphoid"`
+    /// * `path` - Path to a directory from which dictionary files should be searched
+    ///            first before looking into the standard dictionary locations.
+    ///            Pass an empty string in order to only look in standard locations.
+    #[must_use]
+    pub fn default_dictionary(language: &str, path: &str) -> Option<Dictionary> {
+        let (lang, script, variant) = parse_language_tag(language);
+        list_dicts(path).into_iter().find(|dict| {
+            dict.language == lang
+                && (script.is_empty() || dict.script == script)
+                && (variant.is_empty() || dict.variant == variant)
+        })
+    }
+
+    /// Returns the distinct variants of the available dictionaries for given language, in the
+    /// order `list_dicts()` returns them.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - language code, e.g. `"fi"`
+    /// * `path` - Path to a directory from which dictionary files should be searched
+    ///            first before looking into the standard dictionary locations.
+    ///            Pass an empty string in order to only look in standard locations.
+    #[must_use]
+    pub fn variants_for_language(language: &str, path: &str) -> Vec<String> {
+        let mut variants: Vec<String> = Vec::new();
+        for dict in list_dicts(path) {
+            if dict.language == language && !variants.contains(&dict.variant) {
+                variants.push(dict.variant);
+            }
+        }
+        variants
+    }
+
+    /// Returns `true` if at least one dictionary is available.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to a directory from which dictionary files should be searched
+    ///            first before looking into the standard dictionary locations. If `None`,
+    ///            only the standard locations are searched.
+    #[must_use]
+    pub fn dictionaries_available(path: Option<&str>) -> bool {
+        !list_dicts(path.unwrap_or_default()).is_empty()
+    }
+
+    // Adds an explanation to an init error from libvoikko when the language tag is malformed or
+    // matches no installed dictionary.
+    fn explain_init_error(language: &str, path: Option<&str>, error: InitError) -> InitError {
+        if error.source.is_some() {
+            return error;
+        }
+        let (lang, _, _) = parse_language_tag(language);
+        if lang.len() < 2 || lang.len() > 8 || !lang.chars().all(|c| c.is_ascii_alphabetic()) {
+            return InitError::new(&format!("malformed language tag \"{language}\": {error}"));
+        }
+        if !dictionaries_available(path) {
+            return InitError::new(&format!("no dictionaries are installed: {error}"));
+        }
+        if default_dictionary(language, path.unwrap_or_default()).is_some() {
+            error
+        } else {
+            InitError::new(&format!(
+                "no dictionary found for language tag \"{language}\": {error}"
+            ))
+        }
+    }
+
     /// Return a list of language codes representing the languages for which at least one
     /// dictionary is available for spell checking. The codes conform to those specified
     /// in BCP 47. Typically the returned codes consist of only BCP 47 language subtags.
@@ -128,6 +595,219 @@ pub mod voikko {
         libvoikko::list_supported_grammar_checking_languages(path).unwrap_or_else(|_| vec![])
     }
 
+    /// Return a list of language codes for which spell checking, hyphenation and grammar checking
+    /// are all supported, in the order returned by `list_supported_spelling_languages()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to a directory from which dictionary files should be searched
+    ///            first before looking into the standard dictionary locations.
+    ///            Pass an empty string in order to only look in standard locations.
+    pub fn list_fully_supported_languages(path: &str) -> Vec<String> {
+        let hyphenation = list_supported_hyphenation_languages(path);
+        let grammar_checking = list_supported_grammar_checking_languages(path);
+        list_supported_spelling_languages(path)
+            .into_iter()
+            .filter(|lang| hyphenation.contains(lang) && grammar_checking.contains(lang))
+            .collect()
+    }
+
+    /// Same as `list_supported_spelling_languages()` but returns an error if `path` is invalid
+    /// instead of an empty vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to a directory from which dictionary files should be searched
+    ///            first before looking into the standard dictionary locations.
+    ///            Pass an empty string in order to only look in standard locations.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `VoikkoError` if `path` contains a NUL byte.
+    pub fn try_list_supported_spelling_languages(path: &str) -> Result<Vec<String>, VoikkoError> {
+        Ok(libvoikko::list_supported_spelling_languages(path)?)
+    }
+
+    /// Same as `list_supported_hyphenation_languages()` but returns an error if `path` is invalid
+    /// instead of an empty vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to a directory from which dictionary files should be searched
+    ///            first before looking into the standard dictionary locations.
+    ///            Pass an empty string in order to only look in standard locations.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `VoikkoError` if `path` contains a NUL byte.
+    pub fn try_list_supported_hyphenation_languages(path: &str) -> Result<Vec<String>, VoikkoError> {
+        Ok(libvoikko::list_supported_hyphenation_languages(path)?)
+    }
+
+    /// Same as `list_supported_grammar_checking_languages()` but returns an error if `path` is
+    /// invalid instead of an empty vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to a directory from which dictionary files should be searched
+    ///            first before looking into the standard dictionary locations.
+    ///            Pass an empty string in order to only look in standard locations.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `VoikkoError` if `path` contains a NUL byte.
+    pub fn try_list_supported_grammar_checking_languages(
+        path: &str,
+    ) -> Result<Vec<String>, VoikkoError> {
+        Ok(libvoikko::list_supported_grammar_checking_languages(path)?)
+    }
+
+    /// Check that a text can be passed to libvoikko, i.e. that it contains no NUL bytes.
+    /// `&str` is already guaranteed to be valid UTF-8.
+    ///
+    /// `Voikko` methods call this for their text input before passing it to libvoikko. What they
+    /// return for text containing NUL bytes depends on the method: most return an empty result,
+    /// and methods returning a `Result` return an error of the kind they otherwise report, e.g.
+    /// `VoikkoError::Hyphenate` from the hyphenation methods. Calling this function directly
+    /// lets untrusted input be rejected up front with `VoikkoError::Nul` instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - text to validate
+    ///
+    /// # Errors
+    ///
+    /// Returns `VoikkoError::Nul` if the text contains a NUL byte.
+    pub fn validate_input(text: &str) -> Result<(), VoikkoError> {
+        if text.as_bytes().contains(&0) {
+            // NulError has no public constructor, so let CString::new build the error. Its
+            // result is not needed otherwise.
+            std::ffi::CString::new(text)?;
+        }
+        Ok(())
+    }
+
+    // Like validate_input, but returns the NUL error as the error type of the calling method.
+    fn validate_input_as<E: From<std::ffi::NulError>>(text: &str) -> Result<(), E> {
+        match validate_input(text) {
+            Err(VoikkoError::Nul(error)) => Err(E::from(error)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check the spelling of a word with several Voikko instances. Returns `true` if any of
+    /// them reports the word as correct.
+    ///
+    /// # Arguments
+    ///
+    /// * `voikkos` - instances to check with, e.g. one per dictionary variant
+    /// * `word` - word to check
+    #[must_use]
+    pub fn spell_any(voikkos: &[Voikko], word: &str) -> bool {
+        voikkos.iter().any(|v| v.spell(word) == SpellReturn::SpellOk)
+    }
+
+    /// Reconstructs text from tokens by concatenating the token texts in order.
+    ///
+    /// Tokenization is lossless, so `detokenize(&v.tokens(text)) == text` holds for any
+    /// valid input `text`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tokens` - tokens to join, e.g. from `Voikko::tokens()`
+    #[must_use]
+    pub fn detokenize(tokens: &[Token]) -> String {
+        tokens.iter().map(|token| token.token_text.as_str()).collect()
+    }
+
+    // Inserts hyphens into `word` at the hyphenation points marked in `hyphens`, the pattern
+    // returned by libvoikko::hyphens
+    fn apply_hyphens(word: &str, hyphens: &str, opts: &HyphenateStyle) -> String {
+        word.graphemes(true)
+            .zip(hyphens.graphemes(true))
+            .map(|(w, h)| match h {
+                "=" if opts.replace => opts.hyphen.clone(),
+                "-" | "=" => format!("{}{w}", opts.hyphen),
+                _ => String::from(w),
+            })
+            .collect::<String>()
+    }
+
+    // Apostrophes replaced with '\'' by Voikko::tokens if normalize_apostrophes is set
+    const TYPOGRAPHIC_APOSTROPHES: &[char] = &['\u{2019}', '\u{2bc}'];
+
+    // Converts a return value of libvoikko's spell function
+    fn spell_return(code: isize) -> SpellReturn {
+        match code {
+            0 => SpellReturn::SpellFailed,
+            1 => SpellReturn::SpellOk,
+            3 => SpellReturn::CharsetConversionFailed,
+            _ => SpellReturn::InternalError,
+        }
+    }
+
+    // Punctuation stripped by Voikko::spell_trimmed
+    fn is_trimmed_punctuation(c: char) -> bool {
+        use unicode_general_category::{get_general_category, GeneralCategory};
+        c.is_ascii_punctuation()
+            || matches!(
+                get_general_category(c),
+                GeneralCategory::ConnectorPunctuation
+                    | GeneralCategory::DashPunctuation
+                    | GeneralCategory::OpenPunctuation
+                    | GeneralCategory::ClosePunctuation
+                    | GeneralCategory::InitialPunctuation
+                    | GeneralCategory::FinalPunctuation
+                    | GeneralCategory::OtherPunctuation
+            )
+    }
+
+    // Length in bytes of the first `chars` characters of `text`
+    fn char_byte_len(text: &str, chars: usize) -> usize {
+        text.char_indices().nth(chars).map_or(text.len(), |(i, _)| i)
+    }
+
+    // Length in bytes of a token of `token_len` characters at the start of `rest`. A zero
+    // length would never advance the tokenizer, so it is taken to mean the first grapheme.
+    pub(crate) fn token_byte_len(rest: &str, token_len: usize) -> usize {
+        if token_len == 0 {
+            rest.graphemes(true).next().map_or(0, str::len)
+        } else {
+            char_byte_len(rest, token_len)
+        }
+    }
+
+    // Levenshtein distance between `a` and `b` in characters
+    fn levenshtein(a: &str, b: &str) -> usize {
+        let b: Vec<char> = b.chars().collect();
+        let mut row: Vec<usize> = (0..=b.len()).collect();
+        for (i, ca) in a.chars().enumerate() {
+            let mut diagonal = row[0];
+            row[0] = i + 1;
+            for (j, &cb) in b.iter().enumerate() {
+                let substitution = diagonal + usize::from(ca != cb);
+                diagonal = row[j + 1];
+                row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+            }
+        }
+        row[b.len()]
+    }
+
+    // Speller cache size exponent whose cache size in bytes,
+    // 2^exponent * (6544*sizeof(wchar_t) + 1008), is nearest to `approx_bytes`
+    pub(crate) fn speller_cache_exponent(approx_bytes: usize) -> i32 {
+        let mut size = 6544 * std::mem::size_of::<libc::wchar_t>() + 1008;
+        let mut exponent = 0;
+        loop {
+            let next = size.saturating_mul(2);
+            if next == size || next.abs_diff(approx_bytes) >= size.abs_diff(approx_bytes) {
+                return exponent;
+            }
+            size = next;
+            exponent += 1;
+        }
+    }
+
     /// A Voikko instance
     ///
     /// # Example
@@ -142,26 +822,173 @@ pub mod voikko {
     ///                Ok(String::from("kun-nal-lis-po-li-tiik-ka")));
     /// }
     /// ```
+    ///
+    /// # Thread safety
+    ///
+    /// A libvoikko handle must not be used from several threads at the same time, so `Voikko`
+    /// is neither `Send` nor `Sync`. Create a separate instance in each thread that needs one.
+    ///
+    /// ```compile_fail
+    /// use voikko_rs::voikko;
+    ///
+    /// fn assert_sync<T: Sync>() {}
+    /// assert_sync::<voikko::Voikko>();
+    /// ```
     pub struct Voikko {
         handle: *mut libvoikko::VoikkoHandle,
+        options: Cell<VoikkoOptions>,
+        default_desc_lang: RefCell<String>,
+        normalize_apostrophes: Cell<bool>,
+        // grammar error descriptions by (error code, description language)
+        grammar_descriptions: RefCell<HashMap<(i32, String), String>>,
+        // language tag and search path given to Voikko::new, None for from_raw
+        language: Option<String>,
+        path: Option<String>,
     }
 
-    /// A spell check return value
-    #[derive(Debug, PartialEq, Eq)]
-    pub enum SpellReturn {
-        /// Incorrect spelling
-        SpellFailed,
-        /// Correct spelling
-        SpellOk,
-        /// Internal error from libvoikko
-        InternalError,
-        /// libvoikko failed to convert character sets
-        CharsetConversionFailed,
-    }
-
-    /// Type of token returned by [`analyze()`]
-    #[derive(Debug, PartialEq, Eq)]
-    #[allow(missing_docs)]
+    /// Values of all libvoikko options of a `Voikko` instance
+    ///
+    /// The `Default` values are the ones a new libvoikko instance starts with. See the
+    /// corresponding `Voikko::set_*` methods for the meaning of each option.
+    #[allow(clippy::struct_excessive_bools)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct VoikkoOptions {
+        /// Default: false
+        pub ignore_dot: bool,
+        /// Default: false
+        pub ignore_numbers: bool,
+        /// Default: false
+        pub ignore_uppercase: bool,
+        /// Default: true
+        pub accept_first_uppercase: bool,
+        /// Default: true
+        pub accept_all_uppercase: bool,
+        /// Default: false
+        pub no_ugly_hyphenation: bool,
+        /// Default: false
+        pub ocr_suggestions: bool,
+        /// Default: true
+        pub ignore_nonwords: bool,
+        /// Default: false
+        pub accept_extra_hyphens: bool,
+        /// Default: false
+        pub accept_missing_hyphens: bool,
+        /// Default: false
+        pub accept_titles_in_gc: bool,
+        /// Default: false
+        pub accept_unfinished_paragraphs_in_gc: bool,
+        /// Default: true
+        pub hyphenate_unknown_words: bool,
+        /// Default: false
+        pub accept_bulleted_lists_in_gc: bool,
+        /// Default: 2
+        pub min_hyphenated_word_length: i32,
+        /// Default: 0
+        pub speller_cache_size: i32,
+    }
+
+    impl Default for VoikkoOptions {
+        fn default() -> Self {
+            VoikkoOptions {
+                ignore_dot: false,
+                ignore_numbers: false,
+                ignore_uppercase: false,
+                accept_first_uppercase: true,
+                accept_all_uppercase: true,
+                no_ugly_hyphenation: false,
+                ocr_suggestions: false,
+                ignore_nonwords: true,
+                accept_extra_hyphens: false,
+                accept_missing_hyphens: false,
+                accept_titles_in_gc: false,
+                accept_unfinished_paragraphs_in_gc: false,
+                hyphenate_unknown_words: true,
+                accept_bulleted_lists_in_gc: false,
+                min_hyphenated_word_length: 2,
+                speller_cache_size: 0,
+            }
+        }
+    }
+
+    /// A spell check return value
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum SpellReturn {
+        /// Incorrect spelling
+        SpellFailed,
+        /// Correct spelling
+        SpellOk,
+        /// Internal error from libvoikko
+        InternalError,
+        /// libvoikko failed to convert character sets
+        CharsetConversionFailed,
+    }
+
+    impl SpellReturn {
+        /// Converts the spell check result into a `Result`, so that it can be used with `?`.
+        ///
+        /// # Errors
+        ///
+        /// Returns the `SpellError` corresponding to any result other than `SpellOk`.
+        pub fn into_result(self) -> Result<(), SpellError> {
+            match self {
+                SpellReturn::SpellOk => Ok(()),
+                SpellReturn::SpellFailed => Err(SpellError::Misspelled),
+                SpellReturn::InternalError => Err(SpellError::InternalError),
+                SpellReturn::CharsetConversionFailed => Err(SpellError::CharsetConversionFailed),
+            }
+        }
+    }
+
+    /// Failed spell check, returned by [`SpellReturn::into_result()`]
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub enum SpellError {
+        /// Incorrect spelling
+        Misspelled,
+        /// Internal error from libvoikko
+        InternalError,
+        /// libvoikko failed to convert character sets
+        CharsetConversionFailed,
+    }
+
+    impl std::fmt::Display for SpellError {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            match self {
+                SpellError::Misspelled => write!(f, "incorrect spelling"),
+                SpellError::InternalError => write!(f, "internal error in libvoikko"),
+                SpellError::CharsetConversionFailed => {
+                    write!(f, "libvoikko failed to convert character sets")
+                }
+            }
+        }
+    }
+
+    impl error::Error for SpellError {}
+
+    /// Result of a spell check together with suggestions, returned by [`Voikko::check()`]
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct SpellCheck {
+        /// Result of the spell check
+        pub result: SpellReturn,
+        /// Suggested spellings. Empty unless `result` is `SpellFailed`.
+        pub suggestions: Vec<String>,
+    }
+
+    /// A misspelled word found by [`Voikko::review_text()`]
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct ReviewItem {
+        /// The misspelled word
+        pub word: String,
+        /// Start position of the word in the text in bytes
+        pub start: usize,
+        /// Length of the word in bytes
+        pub length: usize,
+        /// Suggested spellings
+        pub suggestions: Vec<String>,
+    }
+
+    /// Type of token returned by [`analyze()`]
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+    #[allow(missing_docs)]
     pub enum TokenType {
         None,
         Word,
@@ -187,9 +1014,74 @@ pub mod voikko {
                 token_type,
             }
         }
+
+        /// Length of the token text in grapheme clusters.
+        #[must_use]
+        pub fn grapheme_len(&self) -> usize {
+            self.token_text.graphemes(true).count()
+        }
+
+        /// Returns `true` for words, punctuation and unknown tokens, and `false` for whitespace
+        /// and `TokenType::None`.
+        #[must_use]
+        pub fn is_significant(&self) -> bool {
+            matches!(
+                self.token_type,
+                TokenType::Word | TokenType::Punctuation | TokenType::Unknown
+            )
+        }
+    }
+
+    /// Tokenization unit borrowing its text, returned by [`Voikko::token_refs()`]
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct TokenRef<'a> {
+        /// Text of the token
+        pub token_text: &'a str,
+        /// Type of the token
+        pub token_type: TokenType,
+    }
+
+    #[allow(missing_docs)]
+    impl<'a> TokenRef<'a> {
+        pub fn new(token_text: &'a str, token_type: TokenType) -> TokenRef<'a> {
+            TokenRef {
+                token_text,
+                token_type,
+            }
+        }
+
+        /// Copies the token text into an owned Token.
+        #[must_use]
+        pub fn to_token(&self) -> Token {
+            Token::new(self.token_text, self.token_type)
+        }
+    }
+
+    impl From<TokenRef<'_>> for Token {
+        fn from(token: TokenRef<'_>) -> Self {
+            token.to_token()
+        }
+    }
+
+    impl From<Token> for (String, TokenType) {
+        fn from(token: Token) -> Self {
+            (token.token_text, token.token_type)
+        }
+    }
+
+    impl From<(String, TokenType)> for Token {
+        fn from((token_text, token_type): (String, TokenType)) -> Self {
+            Token {
+                token_text,
+                token_type,
+            }
+        }
     }
 
     /// Type of a following sentence
+    ///
+    /// Sentence types are ordered by how confidently a new sentence starts, from `None` to
+    /// `Probable`, which is not the order of the variants.
     #[derive(Debug, PartialEq, Eq, Clone, Copy)]
     pub enum SentenceType {
         /// End of text reached or error.
@@ -202,6 +1094,30 @@ pub mod voikko {
         Possible,
     }
 
+    impl SentenceType {
+        // how confidently a new sentence starts, from None to Probable
+        fn confidence(self) -> u8 {
+            match self {
+                SentenceType::None => 0,
+                SentenceType::NoStart => 1,
+                SentenceType::Possible => 2,
+                SentenceType::Probable => 3,
+            }
+        }
+    }
+
+    impl PartialOrd for SentenceType {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for SentenceType {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.confidence().cmp(&other.confidence())
+        }
+    }
+
     /// A sentence
     #[derive(Debug, PartialEq, Eq)]
     pub struct Sentence {
@@ -219,6 +1135,56 @@ pub mod voikko {
                 next_start_type: sentence_type,
             }
         }
+
+        /// Length of the sentence text in grapheme clusters.
+        #[must_use]
+        pub fn grapheme_len(&self) -> usize {
+            self.text.graphemes(true).count()
+        }
+    }
+
+    /// A sentence borrowing its text, returned by [`Voikko::sentence_refs()`]
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct SentenceRef<'a> {
+        /// Text of the sentence
+        pub text: &'a str,
+        /// The type of the next sentence
+        pub next_start_type: SentenceType,
+    }
+
+    #[allow(missing_docs)]
+    impl<'a> SentenceRef<'a> {
+        pub fn new(text: &'a str, next_start_type: SentenceType) -> SentenceRef<'a> {
+            SentenceRef {
+                text,
+                next_start_type,
+            }
+        }
+
+        /// Copies the sentence text into an owned Sentence.
+        #[must_use]
+        pub fn to_sentence(&self) -> Sentence {
+            Sentence::new(self.text, self.next_start_type)
+        }
+    }
+
+    impl From<SentenceRef<'_>> for Sentence {
+        fn from(sentence: SentenceRef<'_>) -> Self {
+            sentence.to_sentence()
+        }
+    }
+
+    /// Word and sentence statistics of a text, returned by `Voikko::text_stats()`
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct TextStats {
+        /// Number of word tokens
+        pub word_count: usize,
+        /// Number of sentences
+        pub sentence_count: usize,
+        /// Average number of words per sentence, or 0 if there are no sentences
+        pub average_sentence_length: f64,
+        /// Number of punctuation tokens
+        pub punctuation_count: usize,
     }
 
     #[derive(Debug, PartialEq, Eq)]
@@ -236,10 +1202,68 @@ pub mod voikko {
         pub description: String,
     }
 
+    /// High-level category of a grammar error, returned by [`GrammarError::category()`]
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub enum GrammarErrorCategory {
+        /// Misspelled word (code 1)
+        Spelling,
+        /// Extra whitespace, or whitespace before punctuation (codes 2–3)
+        Spacing,
+        /// Missing, extra or invalid punctuation, quotation marks or parentheses
+        /// (codes 4–5 and 9–12)
+        Punctuation,
+        /// Letter case at the start of a sentence (codes 6–7)
+        Capitalization,
+        /// Repeated word (code 8)
+        Repetition,
+        /// Verb forms, conjunctions and sentence structure (codes 13–18)
+        Syntax,
+        /// A code not known to this crate
+        Other,
+    }
+
+    impl GrammarError {
+        /// Category of the error, based on its libvoikko error code.
+        #[must_use]
+        pub fn category(&self) -> GrammarErrorCategory {
+            match self.code {
+                1 => GrammarErrorCategory::Spelling,
+                2 | 3 => GrammarErrorCategory::Spacing,
+                4 | 5 | 9..=12 => GrammarErrorCategory::Punctuation,
+                6 | 7 => GrammarErrorCategory::Capitalization,
+                8 => GrammarErrorCategory::Repetition,
+                13..=18 => GrammarErrorCategory::Syntax,
+                _ => GrammarErrorCategory::Other,
+            }
+        }
+
+        /// Returns an iterator over the suggestions for correcting the grammar error.
+        pub fn suggestions(&self) -> impl Iterator<Item = &str> {
+            self.suggestions.iter().map(String::as_str)
+        }
+
+        /// Compares two grammar errors like `==`, except that the suggestions are compared as
+        /// sets, ignoring their order.
+        ///
+        /// # Arguments
+        ///
+        /// * `other` - grammar error to compare with
+        #[must_use]
+        pub fn eq_ignoring_suggestion_order(&self, other: &GrammarError) -> bool {
+            self.code == other.code
+                && self.start_pos == other.start_pos
+                && self.length == other.length
+                && self.description == other.description
+                && self.suggestions().collect::<HashSet<_>>()
+                    == other.suggestions().collect::<HashSet<_>>()
+        }
+    }
+
     #[derive(Debug)]
     /// Error in initializing libvoikko
     pub struct InitError {
         message: String,
+        source: Option<std::ffi::NulError>,
     }
 
     #[allow(missing_docs)]
@@ -247,6 +1271,7 @@ pub mod voikko {
         pub fn new(message: &str) -> InitError {
             InitError {
                 message: String::from(message),
+                source: None,
             }
         }
     }
@@ -258,15 +1283,16 @@ pub mod voikko {
     }
 
     impl error::Error for InitError {
-        fn description(&self) -> &str {
-            self.message.as_str()
+        fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+            self.source.as_ref().map(|error| error as &(dyn error::Error + 'static))
         }
     }
 
     impl std::convert::From<std::ffi::NulError> for InitError {
         fn from(error: std::ffi::NulError) -> Self {
             InitError {
-                message: format!("{}", error)
+                message: format!("{error}"),
+                source: Some(error),
             }
         }
     }
@@ -275,6 +1301,14 @@ pub mod voikko {
     /// Error hyphenating a string
     pub struct HyphenateError {
         message: String,
+        source: Option<FfiErrorSource>,
+    }
+
+    // Underlying error of a HyphenateError or GrammarCheckError
+    #[derive(Debug, PartialEq, Eq)]
+    enum FfiErrorSource {
+        Nul(std::ffi::NulError),
+        Utf8(std::str::Utf8Error),
     }
 
     #[allow(missing_docs)]
@@ -282,262 +1316,2254 @@ pub mod voikko {
         pub fn new(message: &str) -> Self {
             HyphenateError {
                 message: String::from(message),
+                source: None,
+            }
+        }
+    }
+
+    impl std::fmt::Display for HyphenateError {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "{}", self.message)
+        }
+    }
+
+    impl error::Error for HyphenateError {
+        fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+            match &self.source {
+                Some(FfiErrorSource::Nul(error)) => Some(error),
+                Some(FfiErrorSource::Utf8(error)) => Some(error),
+                None => None,
+            }
+        }
+    }
+
+    impl std::convert::From<std::ffi::NulError> for HyphenateError {
+        fn from(error: std::ffi::NulError) -> Self {
+            HyphenateError {
+                message: format!("{error}"),
+                source: Some(FfiErrorSource::Nul(error)),
+            }
+        }
+    }
+
+    impl std::convert::From<std::str::Utf8Error> for HyphenateError {
+        fn from(error: std::str::Utf8Error) -> Self {
+            HyphenateError {
+                message: format!("{error}"),
+                source: Some(FfiErrorSource::Utf8(error)),
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    /// Error checking the grammar of a text
+    pub struct GrammarCheckError {
+        message: String,
+        source: Option<FfiErrorSource>,
+    }
+
+    #[allow(missing_docs)]
+    impl GrammarCheckError {
+        pub fn new(message: &str) -> Self {
+            GrammarCheckError {
+                message: String::from(message),
+                source: None,
+            }
+        }
+    }
+
+    impl std::fmt::Display for GrammarCheckError {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "{}", self.message)
+        }
+    }
+
+    impl error::Error for GrammarCheckError {
+        fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+            match &self.source {
+                Some(FfiErrorSource::Nul(error)) => Some(error),
+                Some(FfiErrorSource::Utf8(error)) => Some(error),
+                None => None,
+            }
+        }
+    }
+
+    impl std::convert::From<std::ffi::NulError> for GrammarCheckError {
+        fn from(error: std::ffi::NulError) -> Self {
+            GrammarCheckError {
+                message: format!("{error}"),
+                source: Some(FfiErrorSource::Nul(error)),
+            }
+        }
+    }
+
+    impl std::convert::From<std::str::Utf8Error> for GrammarCheckError {
+        fn from(error: std::str::Utf8Error) -> Self {
+            GrammarCheckError {
+                message: format!("{error}"),
+                source: Some(FfiErrorSource::Utf8(error)),
+            }
+        }
+    }
+
+    /// Hyphenation style for [`Voikko::hyphenate_with()`]
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct HyphenateStyle {
+        /// String to insert at hyphenation points
+        pub hyphen: String,
+        /// Whether the character at a `'='` hyphenation point is replaced by the hyphen (`true`)
+        /// or kept after it (`false`)
+        pub replace: bool,
+    }
+
+    impl HyphenateStyle {
+        /// Construct new `HyphenateStyle` that replaces characters at `'='` hyphenation points,
+        /// like `Voikko::hyphenate()` does.
+        ///
+        /// # Arguments
+        ///
+        /// * `hyphen` - string to insert at hyphenation points
+        #[must_use]
+        pub fn new(hyphen: &str) -> HyphenateStyle {
+            HyphenateStyle {
+                hyphen: String::from(hyphen),
+                replace: true,
+            }
+        }
+    }
+
+    impl Default for HyphenateStyle {
+        fn default() -> Self {
+            HyphenateStyle::new("-")
+        }
+    }
+
+    /// Hyphenation points of a word, as returned by [`Voikko::hyphenation()`]
+    ///
+    /// Wraps the hyphenation string in the notation of [`Voikko::hyphens()`], with one
+    /// character for each character of the word.
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct Hyphenation(String);
+
+    impl Hyphenation {
+        /// Construct new `Hyphenation` from a hyphenation string.
+        #[must_use]
+        pub fn new(hyphens: &str) -> Hyphenation {
+            Hyphenation(String::from(hyphens))
+        }
+
+        /// The hyphenation string.
+        #[must_use]
+        pub fn as_str(&self) -> &str {
+            &self.0
+        }
+
+        /// Character indices of the hyphenation points, marked with `'-'` or `'='`.
+        #[must_use]
+        pub fn points(&self) -> Vec<usize> {
+            self.0
+                .chars()
+                .enumerate()
+                .filter(|&(_, c)| c == '-' || c == '=')
+                .map(|(i, _)| i)
+                .collect()
+        }
+
+        /// Inserts `hyphen` into `word` at the hyphenation points, like
+        /// [`Voikko::hyphenate()`].
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - the hyphenated word
+        /// * `hyphen` - string to insert at hyphenation points
+        #[must_use]
+        pub fn apply(&self, word: &str, hyphen: &str) -> String {
+            apply_hyphens(word, &self.0, &HyphenateStyle::new(hyphen))
+        }
+    }
+
+    #[derive(Debug)]
+    /// Error from a voikko-rs operation
+    pub enum VoikkoError {
+        /// The input contained an interior NUL byte
+        Nul(std::ffi::NulError),
+        /// The input was not valid UTF-8
+        Utf8(std::str::Utf8Error),
+        /// Reading the input failed
+        Io(std::io::Error),
+        /// libvoikko failed to hyphenate a word
+        Hyphenate(HyphenateError),
+        /// libvoikko failed to check the grammar of a text
+        GrammarCheck(GrammarCheckError),
+    }
+
+    impl std::fmt::Display for VoikkoError {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            match self {
+                VoikkoError::Nul(error) => write!(f, "{error}"),
+                VoikkoError::Utf8(error) => write!(f, "{error}"),
+                VoikkoError::Io(error) => write!(f, "{error}"),
+                VoikkoError::Hyphenate(error) => write!(f, "{error}"),
+                VoikkoError::GrammarCheck(error) => write!(f, "{error}"),
+            }
+        }
+    }
+
+    impl error::Error for VoikkoError {
+        fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+            match self {
+                VoikkoError::Nul(error) => Some(error),
+                VoikkoError::Utf8(error) => Some(error),
+                VoikkoError::Io(error) => Some(error),
+                VoikkoError::Hyphenate(error) => Some(error),
+                VoikkoError::GrammarCheck(error) => Some(error),
+            }
+        }
+    }
+
+    impl std::convert::From<std::ffi::NulError> for VoikkoError {
+        fn from(error: std::ffi::NulError) -> Self {
+            VoikkoError::Nul(error)
+        }
+    }
+
+    impl std::convert::From<std::str::Utf8Error> for VoikkoError {
+        fn from(error: std::str::Utf8Error) -> Self {
+            VoikkoError::Utf8(error)
+        }
+    }
+
+    impl std::convert::From<std::io::Error> for VoikkoError {
+        fn from(error: std::io::Error) -> Self {
+            VoikkoError::Io(error)
+        }
+    }
+
+    impl std::convert::From<HyphenateError> for VoikkoError {
+        fn from(error: HyphenateError) -> Self {
+            VoikkoError::Hyphenate(error)
+        }
+    }
+
+    impl std::convert::From<GrammarCheckError> for VoikkoError {
+        fn from(error: GrammarCheckError) -> Self {
+            VoikkoError::GrammarCheck(error)
+        }
+    }
+
+    // Iterator over the tokens of a text as (text, type) pairs. The text is copied into a
+    // NUL-terminated buffer once, and libvoikko is given a pointer into it for each token.
+    struct RawTokens<'a, 't> {
+        voikko: &'a Voikko,
+        text: &'t str,
+        text_cstring: std::ffi::CString,
+        // position of the next token in bytes
+        offset: usize,
+        // whether libvoikko stopped before the end of the text
+        stopped: bool,
+    }
+
+    impl<'a, 't> RawTokens<'a, 't> {
+        fn new(voikko: &'a Voikko, text: &'t str) -> Result<Self, std::ffi::NulError> {
+            Ok(RawTokens {
+                voikko,
+                text,
+                text_cstring: std::ffi::CString::new(text)?,
+                offset: 0,
+                stopped: false,
+            })
+        }
+    }
+
+    impl<'t> Iterator for RawTokens<'_, 't> {
+        type Item = (&'t str, TokenType);
+
+        #[allow(clippy::match_wildcard_for_single_variants)]
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.offset >= self.text.len() {
+                return None;
+            }
+            let rest = &self.text[self.offset..];
+            let (raw_token, token_len) =
+                libvoikko::next_token(self.voikko.handle, &self.text_cstring, self.offset);
+            let token_type = match raw_token {
+                libvoikko::voikko_token_type::TOKEN_NONE => TokenType::None,
+                libvoikko::voikko_token_type::TOKEN_PUNCTUATION => TokenType::Punctuation,
+                libvoikko::voikko_token_type::TOKEN_WHITESPACE => TokenType::Whitespace,
+                libvoikko::voikko_token_type::TOKEN_WORD => TokenType::Word,
+                _ => TokenType::Unknown,
+            };
+            if token_type == TokenType::None {
+                self.offset = self.text.len();
+                self.stopped = true;
+                return Some((rest, TokenType::Unknown));
+            }
+            let token_text = &rest[..token_byte_len(rest, token_len)];
+            self.offset += token_text.len();
+            Some((token_text, token_type))
+        }
+    }
+
+    /// Spell checker that reuses one buffer for all words
+    ///
+    /// `Voikko::spell()` allocates a NUL-terminated copy of every word. A `SpellSession` copies
+    /// the words into the same buffer instead, which only grows when a longer word is checked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use voikko_rs::voikko;
+    /// let v = voikko::Voikko::new("fi-x-morphoid", None).unwrap();
+    /// let mut session = v.spell_session();
+    /// for word in &["kissa", "kuningas"] {
+    ///     assert_eq!(session.spell(word), voikko::SpellReturn::SpellOk);
+    /// }
+    /// ```
+    pub struct SpellSession<'a> {
+        voikko: &'a Voikko,
+        buffer: Vec<u8>,
+    }
+
+    impl<'a> SpellSession<'a> {
+        /// Construct new `SpellSession` checking with given Voikko instance.
+        #[must_use]
+        pub fn new(voikko: &'a Voikko) -> SpellSession<'a> {
+            SpellSession {
+                voikko,
+                buffer: Vec::new(),
+            }
+        }
+
+        /// Check the spelling of a UTF-8 character string, like `Voikko::spell()`.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to check
+        pub fn spell(&mut self, word: &str) -> SpellReturn {
+            self.buffer.clear();
+            self.buffer.extend_from_slice(word.as_bytes());
+            self.buffer.push(0);
+            match std::ffi::CStr::from_bytes_with_nul(&self.buffer) {
+                Ok(word_cstr) => spell_return(libvoikko::spell_cstr(self.voikko.handle, word_cstr)),
+                // interior NUL byte
+                Err(_) => SpellReturn::SpellFailed,
+            }
+        }
+    }
+
+    // Number of bytes requested from the reader at a time by ReaderTokens
+    const READER_CHUNK_SIZE: usize = 8192;
+
+    // Number of buffered bytes after which ReaderTokens stops waiting for whitespace before
+    // returning tokens, so that input without whitespace is not held in memory indefinitely
+    const READER_MAX_BUFFER: usize = 16 * READER_CHUNK_SIZE;
+
+    // Byte position in `text` of the last non-whitespace character that follows whitespace,
+    // looking at `text[from..]` only. `from` must be a character boundary.
+    fn last_whitespace_break(text: &str, from: usize) -> Option<usize> {
+        let mut last = None;
+        let mut prev_whitespace = false;
+        for (i, c) in text[from..].char_indices() {
+            if prev_whitespace && !c.is_whitespace() {
+                last = Some(from + i);
+            }
+            prev_whitespace = c.is_whitespace();
+        }
+        last
+    }
+
+    /// Iterator over the tokens of a reader
+    ///
+    /// Returned by [`Voikko::tokenize_reader()`].
+    pub struct ReaderTokens<'a, R> {
+        voikko: &'a Voikko,
+        reader: R,
+        buffer: Vec<u8>,
+        // bytes at the start of the buffer already searched for whitespace breaks
+        searched: usize,
+        pending: VecDeque<Token>,
+        eof: bool,
+    }
+
+    impl<R: Read> ReaderTokens<'_, R> {
+        // Read the next chunk of input and tokenize as much of the buffer as can be tokenized
+        // without knowing what comes after it. Each part of the input is tokenized only once.
+        fn fill(&mut self) -> Result<(), VoikkoError> {
+            let mut chunk = [0; READER_CHUNK_SIZE];
+            let read_len = loop {
+                match self.reader.read(&mut chunk) {
+                    Ok(len) => break len,
+                    Err(error) if error.kind() == std::io::ErrorKind::Interrupted => {}
+                    Err(error) => return Err(error.into()),
+                }
+            };
+            if read_len == 0 {
+                self.eof = true;
+            }
+            self.buffer.extend_from_slice(&chunk[..read_len]);
+
+            // a multi-byte character may be split between two chunks
+            let text = match std::str::from_utf8(&self.buffer) {
+                Ok(text) => text,
+                Err(error) if error.error_len().is_none() && !self.eof => {
+                    std::str::from_utf8(&self.buffer[..error.valid_up_to()])?
+                }
+                Err(error) => return Err(error.into()),
+            };
+            // the text before `searched` was validated when it was searched
+            validate_input(&text[self.searched..])?;
+
+            // the tokens up to the last whitespace that is followed by something else are final,
+            // as the last word of the buffer may continue in the next chunk
+            let tokens = if self.eof {
+                self.voikko.tokens(text)
+            } else if let Some(end) = last_whitespace_break(text, self.searched) {
+                self.voikko.tokens(&text[..end])
+            } else if text.len() >= READER_MAX_BUFFER {
+                // hold back only the last token, or nothing if the whole buffer is one token
+                let mut tokens = self.voikko.tokens(text);
+                if tokens.len() > 1 {
+                    tokens.pop();
+                }
+                tokens
+            } else {
+                // search again from the last character, as it may be whitespace
+                self.searched = text.char_indices().last().map_or(0, |(i, _)| i);
+                return Ok(());
+            };
+            let consumed: usize = tokens.iter().map(|token| token.token_text.len()).sum();
+            self.buffer.drain(..consumed);
+            self.searched = 0;
+            self.pending.extend(tokens);
+            Ok(())
+        }
+    }
+
+    impl<R: Read> Iterator for ReaderTokens<'_, R> {
+        type Item = Result<Token, VoikkoError>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            loop {
+                if let Some(token) = self.pending.pop_front() {
+                    return Some(Ok(token));
+                }
+                if self.eof {
+                    return None;
+                }
+                if let Err(error) = self.fill() {
+                    // stop iterating after an error
+                    self.eof = true;
+                    self.buffer.clear();
+                    return Some(Err(error));
+                }
+            }
+        }
+    }
+
+    impl Voikko {
+        /// Initializes Voikko and returns a `Result<Voikko, InitError>`
+        ///
+        /// The returned instance can only be used in the thread that created it. See
+        /// [Thread safety](#thread-safety).
+        ///
+        /// # Arguments
+        ///
+        /// * `language` - BCP 47 language tag for the language to be used.
+        ///                Private use subtags can be used to specify the dictionary variant.
+        /// * `path` - Path to a directory from which dictionary files should be searched first before
+        ///            looking into the standard dictionary locations. If `None`, no additional search path
+        ///            will be used.
+        ///
+        /// # Errors
+        ///
+        /// Returns an `InitError` result if init fails. The error message tells whether the language
+        /// tag was malformed or no installed dictionary matched it.
+        #[must_use = "dropping the Voikko instance terminates it immediately"]
+        pub fn new(language: &str, path: Option<&str>) -> Result<Voikko, InitError> {
+            let v = libvoikko::init(language, path);
+
+            match v {
+                Ok(handle) => {
+                    let mut voikko = Voikko::with_handle(handle);
+                    voikko.language = Some(String::from(language));
+                    voikko.path = path.map(String::from);
+                    Ok(voikko)
+                }
+                Err(error) => Err(explain_init_error(language, path, error)),
+            }
+        }
+
+        /// Terminates this Voikko instance, releasing the libvoikko handle immediately instead of
+        /// when the instance goes out of scope.
+        pub fn close(mut self) {
+            libvoikko::terminate(self.handle);
+            // keep Drop from terminating the handle a second time
+            self.handle = std::ptr::null_mut();
+        }
+
+        /// Consumes this instance and returns its raw libvoikko handle without terminating it.
+        ///
+        /// The caller becomes responsible for terminating the handle, either by passing it to
+        /// libvoikko's `voikkoTerminate` or by converting it back with `Voikko::from_raw()`.
+        #[must_use = "the handle is leaked unless it is terminated"]
+        pub fn into_raw(mut self) -> *mut VoikkoHandle {
+            std::mem::replace(&mut self.handle, std::ptr::null_mut())
+        }
+
+        /// Constructs a `Voikko` instance from a raw libvoikko handle. The instance takes ownership
+        /// of the handle and terminates it when dropped.
+        ///
+        /// # Arguments
+        ///
+        /// * `handle` - handle returned by `Voikko::into_raw()` or libvoikko's `voikkoInit`
+        ///
+        /// # Safety
+        ///
+        /// `handle` must be a valid libvoikko handle that is not owned by anything else, and must
+        /// not be used after the returned instance has been dropped. A null handle is also accepted:
+        /// dropping the instance then does nothing, but no other method may be called on it.
+        ///
+        /// Options changed on the handle before the conversion are not known to the new instance,
+        /// so `Voikko::options()` reports the defaults until they are set again.
+        pub unsafe fn from_raw(handle: *mut VoikkoHandle) -> Voikko {
+            Voikko::with_handle(handle)
+        }
+
+        fn with_handle(handle: *mut VoikkoHandle) -> Voikko {
+            Voikko {
+                handle,
+                options: Cell::new(VoikkoOptions::default()),
+                default_desc_lang: RefCell::new(String::from("en")),
+                normalize_apostrophes: Cell::new(false),
+                grammar_descriptions: RefCell::new(HashMap::new()),
+                language: None,
+                path: None,
+            }
+        }
+
+        /// Returns `true` if grammar checking is supported for the language this instance was
+        /// initialized with. Always `false` for instances created with `from_raw()`, whose
+        /// language is not known.
+        #[must_use]
+        pub fn supports_grammar_checking(&self) -> bool {
+            self.supports(list_supported_grammar_checking_languages)
+        }
+
+        /// Returns `true` if hyphenation is supported for the language this instance was
+        /// initialized with. Always `false` for instances created with `from_raw()`, whose
+        /// language is not known.
+        #[must_use]
+        pub fn supports_hyphenation(&self) -> bool {
+            self.supports(list_supported_hyphenation_languages)
+        }
+
+        // Whether the language of this instance is in the list returned by `list_languages`
+        fn supports<F: Fn(&str) -> Vec<String>>(&self, list_languages: F) -> bool {
+            let Some(language) = &self.language else {
+                return false;
+            };
+            let (lang, _, _) = parse_language_tag(language);
+            list_languages(self.path.as_deref().unwrap_or_default())
+                .iter()
+                .any(|supported| parse_language_tag(supported).0 == lang)
+        }
+
+        /// Returns the raw libvoikko handle of this instance, for calling libvoikko functions that
+        /// are not wrapped by this crate.
+        ///
+        /// # Examples
+        ///
+        /// ```no_run
+        /// use std::os::raw::{c_char, c_int};
+        /// use voikko_rs::voikko;
+        ///
+        /// #[link(name = "voikko")]
+        /// extern "C" {
+        ///     fn voikkoSpellCstr(handle: *mut voikko::VoikkoHandle, word: *const c_char) -> c_int;
+        /// }
+        ///
+        /// let v = voikko::Voikko::new("fi-x-morphoid", None).unwrap();
+        /// let word = std::ffi::CString::new("kuningas").unwrap();
+        /// let ret = unsafe { voikkoSpellCstr(v.raw_handle(), word.as_ptr()) };
+        /// assert_eq!(ret, 1);
+        /// ```
+        ///
+        /// # Safety
+        ///
+        /// The handle is owned by this `Voikko` instance. Using it after the instance has been
+        /// dropped or closed, terminating it, or using it from several threads at once is
+        /// undefined behavior. Options changed directly through the handle are not known to this
+        /// crate. Calling libvoikko functions with invalid arguments is undefined behavior too.
+        pub unsafe fn raw_handle(&self) -> *mut VoikkoHandle {
+            self.handle
+        }
+
+        /// Check the spelling of a UTF-8 character string.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to check
+        #[must_use]
+        pub fn spell(&self, word: &str) -> SpellReturn {
+            if validate_input(word).is_err() {
+                return SpellReturn::SpellFailed;
+            }
+            let ret = libvoikko::spell(self.handle, word);
+            match ret {
+                Ok(code) => spell_return(code),
+                Err(_) => SpellReturn::SpellFailed,
+            }
+        }
+
+        /// Returns a `SpellSession` for checking the spelling of many words without allocating
+        /// for each word.
+        #[must_use]
+        pub fn spell_session(&self) -> SpellSession<'_> {
+            SpellSession::new(self)
+        }
+
+        /// Check the spelling of a UTF-8 character string, ignoring differences in case.
+        ///
+        /// The word is first checked as-is. If that fails, its lowercase form is checked.
+        /// The case options of this instance (such as `set_opt_accept_all_uppercase()`) are not
+        /// modified. Note that this accepts words that are only correct when capitalized
+        /// differently, e.g. a common noun written in mixed case.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to check
+        #[must_use]
+        pub fn spell_case_insensitive(&self, word: &str) -> SpellReturn {
+            if validate_input(word).is_err() {
+                return SpellReturn::SpellFailed;
+            }
+            match self.spell(word) {
+                SpellReturn::SpellFailed => self.spell(&word.to_lowercase()),
+                ret => ret,
+            }
+        }
+
+        /// Check the spelling of a UTF-8 character string and find suggested correct spellings
+        /// if it is misspelled.
+        ///
+        /// Suggestions are only looked up when the result is `SpellFailed`.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to check
+        #[must_use]
+        pub fn check(&self, word: &str) -> SpellCheck {
+            if validate_input(word).is_err() {
+                return SpellCheck {
+                    result: SpellReturn::SpellFailed,
+                    suggestions: vec![],
+                };
+            }
+            let result = self.spell(word);
+            let suggestions = if result == SpellReturn::SpellFailed {
+                self.suggest(word)
+            } else {
+                vec![]
+            };
+            SpellCheck {
+                result,
+                suggestions,
+            }
+        }
+
+        /// Check the spelling of a UTF-8 character string with `set_opt_ignore_dot()` set to
+        /// `ignore_dot` for this call only. The previous value of the option is restored
+        /// afterwards.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to check
+        /// * `ignore_dot` - whether to retry without a trailing dot
+        #[must_use]
+        pub fn spell_with_dot_handling(&self, word: &str, ignore_dot: bool) -> SpellReturn {
+            if validate_input(word).is_err() {
+                return SpellReturn::SpellFailed;
+            }
+            let previous = self.options().ignore_dot;
+            self.set_opt_ignore_dot(ignore_dot);
+            let ret = self.spell(word);
+            self.set_opt_ignore_dot(previous);
+            ret
+        }
+
+        /// Check the spelling of a UTF-8 character string, ignoring punctuation around it.
+        ///
+        /// If the word is not correct as-is (abbreviations such as "esim." include their dot),
+        /// leading and trailing punctuation is stripped and the remaining word is checked. The
+        /// stripped characters are ASCII punctuation and characters in the Unicode punctuation
+        /// general categories, such as `«`, `»`, `‹`, `›`, `¡` and `¿`.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to check
+        #[must_use]
+        pub fn spell_trimmed(&self, word: &str) -> SpellReturn {
+            if validate_input(word).is_err() {
+                return SpellReturn::SpellFailed;
+            }
+            match self.spell(word) {
+                SpellReturn::SpellFailed => {
+                    let trimmed = word.trim_matches(is_trimmed_punctuation);
+                    if trimmed.is_empty() || trimmed.len() == word.len() {
+                        SpellReturn::SpellFailed
+                    } else {
+                        self.spell(trimmed)
+                    }
+                }
+                ret => ret,
+            }
+        }
+
+        /// Check the spelling of a UTF-8 character string after normalizing its case.
+        ///
+        /// Lowercasing a word before checking it turns proper nouns such as "Helsinki" into
+        /// misspellings. Instead, the word is checked as-is, then capitalized (first letter in
+        /// uppercase, the rest in lowercase) and finally in lowercase, until one of the forms is
+        /// correct. With `set_opt_accept_first_uppercase(true)` (the default) the capitalized form
+        /// also covers common words, so the lowercase form is only needed if that option is off.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to check
+        #[must_use]
+        pub fn spell_normalized(&self, word: &str) -> SpellReturn {
+            if validate_input(word).is_err() {
+                return SpellReturn::SpellFailed;
+            }
+            let mut chars = word.chars();
+            let capitalized = match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars.as_str().to_lowercase().chars()).collect(),
+                None => String::new(),
+            };
+            match self.spell(word) {
+                SpellReturn::SpellFailed => match self.spell(&capitalized) {
+                    SpellReturn::SpellFailed => self.spell(&word.to_lowercase()),
+                    ret => ret,
+                },
+                ret => ret,
+            }
+        }
+
+        /// Check the spelling of every word in a text string. Returns the misspelled words with
+        /// their positions and suggested spellings, in the order they appear in the text.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to check.
+        #[must_use]
+        pub fn review_text(&self, text: &str) -> Vec<ReviewItem> {
+            if validate_input(text).is_err() {
+                return vec![];
+            }
+            let mut items = Vec::new();
+            let mut offset = 0;
+            for token in self.tokens(text) {
+                let length = token.token_text.len();
+                if token.token_type == TokenType::Word {
+                    let check = self.check(&token.token_text);
+                    if check.result == SpellReturn::SpellFailed {
+                        items.push(ReviewItem {
+                            word: token.token_text,
+                            start: offset,
+                            length,
+                            suggestions: check.suggestions,
+                        });
+                    }
+                }
+                offset += length;
+            }
+            items
+        }
+
+        /// Check the spelling of one word per line from `input` and write one result line per
+        /// input line to `output`.
+        ///
+        /// Each line is trimmed before checking. The result line is the word followed by a tab
+        /// and `OK`, `WRONG` or `ERROR` (for libvoikko errors). Empty lines are copied as empty
+        /// lines, so output lines always match input lines.
+        ///
+        /// # Arguments
+        ///
+        /// * `input` - Reader to read UTF-8 encoded words from, e.g. `std::io::stdin().lock()`.
+        /// * `output` - Writer to write the results to.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if reading or writing fails, or if the input is not valid UTF-8.
+        pub fn spell_lines<R: BufRead, W: Write>(&self, input: R, mut output: W) -> std::io::Result<()> {
+            for line in input.lines() {
+                let line = line?;
+                let word = line.trim();
+                if word.is_empty() {
+                    writeln!(output)?;
+                    continue;
+                }
+                let result = match self.spell(word) {
+                    SpellReturn::SpellOk => "OK",
+                    SpellReturn::SpellFailed => "WRONG",
+                    _ => "ERROR",
+                };
+                writeln!(output, "{word}\t{result}")?;
+            }
+            output.flush()
+        }
+
+        /// Finds suggested correct spellings for given UTF-8 encoded word.
+        /// Returns a vector of strings - an empty vector, if no suggestions.
+        /// Invalid UTF-8 in suggestions is replaced with U+FFFD REPLACEMENT CHARACTER.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to find suggestions for
+        #[must_use]
+        pub fn suggest(&self, word: &str) -> Vec<String> {
+            if validate_input(word).is_err() {
+                return vec![];
+            }
+            libvoikko::suggest(self.handle, word).unwrap_or_else(|_| vec![])
+        }
+
+        /// Finds at most `max` suggested correct spellings for given UTF-8 encoded word, like
+        /// `suggest()`. The cap is applied while collecting the suggestions from libvoikko, so
+        /// a long suggestion list never gets copied in full.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to find suggestions for
+        /// * `max` - maximum number of suggestions to return
+        #[must_use]
+        pub fn suggest_n(&self, word: &str, max: usize) -> Vec<String> {
+            if validate_input(word).is_err() {
+                return vec![];
+            }
+            libvoikko::suggest_limit(self.handle, word, max).unwrap_or_else(|_| vec![])
+        }
+
+        /// Finds suggested correct spellings for given UTF-8 encoded word, like `suggest()`.
+        /// Returns an iterator of `(rank, suggestion)` pairs in libvoikko's order, with ranks
+        /// starting at 1.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to find suggestions for
+        pub fn suggest_ranked(&self, word: &str) -> impl Iterator<Item = (usize, String)> {
+            let suggestions = if validate_input(word).is_ok() { self.suggest(word) } else { vec![] };
+            suggestions
+                .into_iter()
+                .enumerate()
+                .map(|(i, suggestion)| (i + 1, suggestion))
+        }
+
+        /// Finds the top suggested correct spelling for given UTF-8 encoded word, like
+        /// `suggest()`, together with its Levenshtein distance in characters to the word.
+        /// Returns `None` if there are no suggestions.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to find a suggestion for
+        #[must_use]
+        pub fn best_suggestion(&self, word: &str) -> Option<(String, usize)> {
+            if validate_input(word).is_err() {
+                return None;
+            }
+            let suggestion = self.suggest_n(word, 1).pop()?;
+            let distance = levenshtein(word, &suggestion);
+            Some((suggestion, distance))
+        }
+
+        /// Hyphenates the given word in UTF-8 encoding.
+        /// Returns a string containing the hyphenation using the following notation:
+        /// * `' '` = no hyphenation at this character,
+        /// * `'-'` = hyphenation point (character at this position
+        ///        is preserved in the hyphenated form),
+        /// * `'='` = hyphenation point (character at this position
+        ///        is replaced by the hyphen.)
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to hyphenate
+        ///
+        /// # Errors
+        ///
+        /// Returns an error result on error. Use `try_hyphens()` to find out what the error was.
+        pub fn hyphens(&self, word: &str) -> Result<String, bool> {
+            if validate_input(word).is_err() {
+                return Err(false);
+            }
+            libvoikko::hyphens(self.handle, word).map_err(|_| false)
+        }
+
+        /// Hyphenates the given word in UTF-8 encoding, like `hyphens()`, but returns a
+        /// `VoikkoError` describing the failure instead of `false`.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to hyphenate
+        ///
+        /// # Errors
+        ///
+        /// Returns a `VoikkoError` if `word` contains a NUL byte or libvoikko fails to hyphenate it.
+        pub fn try_hyphens(&self, word: &str) -> Result<String, VoikkoError> {
+            validate_input_as::<HyphenateError>(word)?;
+            Ok(libvoikko::hyphens(self.handle, word)?)
+        }
+
+        /// Hyphenates the given word in UTF-8 encoding, like `try_hyphens()`, but returns the
+        /// hyphenation points as a `Hyphenation`.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to hyphenate
+        ///
+        /// # Errors
+        ///
+        /// Returns a `VoikkoError` if `word` contains a NUL byte or libvoikko fails to hyphenate it.
+        pub fn hyphenation(&self, word: &str) -> Result<Hyphenation, VoikkoError> {
+            validate_input_as::<HyphenateError>(word)?;
+            self.try_hyphens(word).map(Hyphenation)
+        }
+
+        /// Hyphenates the given word in UTF-8 encoding.
+        /// Returns a string where caller-supplied characters are inserted in all hyphenation points.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to hyphenate
+        /// * `hyphen` - string to insert at hyphenation points
+        ///
+        /// # Errors
+        ///
+        /// Returns an error result on error.
+        pub fn hyphenate(&self, word: &str, hyphen: &str) -> Result<String, bool> {
+            if validate_input(word).is_err() {
+                return Err(false);
+            }
+            self.hyphenate_with(word, &HyphenateStyle::new(hyphen)).map_err(|_| false)
+        }
+
+        /// Hyphenates the given word in UTF-8 encoding.
+        /// Returns a string where the hyphen given in `opts` is inserted in all hyphenation points.
+        /// Unlike `hyphenate()`, the character at a hyphenation point marked with `'='` by
+        /// `hyphens()` can be kept instead of replaced by the hyphen.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to hyphenate
+        /// * `opts` - hyphen string and handling of replaced characters
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use voikko_rs::voikko;
+        /// # let v = voikko::Voikko::new("fi-x-morphoid", None).unwrap();
+        /// let mut style = voikko::HyphenateStyle::new("-");
+        /// style.replace = false;
+        /// assert_eq!(v.hyphenate_with("rei'ittää", &style).unwrap(), "rei-'it-tää");
+        /// ```
+        ///
+        /// # Errors
+        ///
+        /// Returns a `VoikkoError` if `word` contains a NUL byte or libvoikko fails to hyphenate it.
+        pub fn hyphenate_with(&self, word: &str, opts: &HyphenateStyle) -> Result<String, VoikkoError> {
+            validate_input_as::<HyphenateError>(word)?;
+            let hyphens = libvoikko::hyphens(self.handle, word)?;
+            Ok(apply_hyphens(word, &hyphens, opts))
+        }
+
+        /// Hyphenates the given word in UTF-8 encoding, inserting U+00AD SOFT HYPHEN at all
+        /// hyphenation points, e.g. for HTML output. Like `hyphenate()`, the character at a `'='`
+        /// hyphenation point is replaced by the soft hyphen, except for a hyphen `'-'`, which is
+        /// kept as is because a line can already be broken after it.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to hyphenate
+        ///
+        /// # Errors
+        ///
+        /// Returns a `VoikkoError` if `word` contains a NUL byte or libvoikko fails to hyphenate it.
+        pub fn hyphenate_soft(&self, word: &str) -> Result<String, VoikkoError> {
+            validate_input_as::<HyphenateError>(word)?;
+            let hyphens: String = word
+                .graphemes(true)
+                .zip(libvoikko::hyphens(self.handle, word)?.graphemes(true))
+                .map(|(w, h)| if w == "-" && h == "=" { " " } else { h })
+                .collect();
+            Ok(apply_hyphens(word, &hyphens, &HyphenateStyle::new("\u{ad}")))
+        }
+
+        /// Hyphenates the given word like `hyphenate()`, but leaves out hyphenation points that
+        /// would leave fewer than `min_lead` characters before the hyphen or fewer than
+        /// `min_trail` characters after it. Characters are counted as grapheme clusters, so e.g. a
+        /// letter followed by a combining mark counts as one.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to hyphenate
+        /// * `hyphen` - string to insert at hyphenation points
+        /// * `min_lead` - minimum number of characters before a hyphenation point
+        /// * `min_trail` - minimum number of characters after a hyphenation point
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use voikko_rs::voikko;
+        /// # let v = voikko::Voikko::new("fi-x-morphoid", None).unwrap();
+        /// assert_eq!(v.hyphenate_with_orphans("kaljakori", "-", 4, 2).unwrap(), "kalja-ko-ri");
+        /// ```
+        ///
+        /// # Errors
+        ///
+        /// Returns a `VoikkoError` if `word` contains a NUL byte or libvoikko fails to hyphenate it.
+        pub fn hyphenate_with_orphans(
+            &self,
+            word: &str,
+            hyphen: &str,
+            min_lead: usize,
+            min_trail: usize,
+        ) -> Result<String, VoikkoError> {
+            validate_input_as::<HyphenateError>(word)?;
+            let hyphens = libvoikko::hyphens(self.handle, word)?;
+            // count positions the same way as apply_hyphens, which pairs them with graphemes
+            let len = word.graphemes(true).count();
+            let allowed: String = hyphens
+                .graphemes(true)
+                .take(len)
+                .enumerate()
+                .map(|(i, h)| {
+                    // the character at a '=' point is replaced by the hyphen
+                    let trail = if h == "=" { len - i - 1 } else { len - i };
+                    if i < min_lead || trail < min_trail {
+                        " "
+                    } else {
+                        h
+                    }
+                })
+                .collect();
+            Ok(apply_hyphens(word, &allowed, &HyphenateStyle::new(hyphen)))
+        }
+
+        /// Hyphenates the given word like `hyphenate()`, but borrows the word instead of copying
+        /// it if it has no hyphenation points.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to hyphenate
+        /// * `hyphen` - string to insert at hyphenation points
+        ///
+        /// # Errors
+        ///
+        /// Returns a `VoikkoError` if `word` contains a NUL byte or libvoikko fails to hyphenate it.
+        pub fn hyphenate_cow<'a>(&self, word: &'a str, hyphen: &str) -> Result<Cow<'a, str>, VoikkoError> {
+            validate_input_as::<HyphenateError>(word)?;
+            let hyphens = libvoikko::hyphens(self.handle, word)?;
+            if hyphens.contains(['-', '=']) {
+                Ok(Cow::Owned(apply_hyphens(word, &hyphens, &HyphenateStyle::new(hyphen))))
+            } else {
+                Ok(Cow::Borrowed(word))
+            }
+        }
+
+        /// Hyphenates each of the given words like `hyphenate()`. Returns a vector with a result
+        /// for each word, in the same order as the words.
+        ///
+        /// # Arguments
+        ///
+        /// * `words` - words to hyphenate
+        /// * `hyphen` - string to insert at hyphenation points
+        pub fn hyphenate_many<'a, I: IntoIterator<Item = &'a str>>(
+            &self,
+            words: I,
+            hyphen: &str,
+        ) -> Vec<Result<String, VoikkoError>> {
+            let style = HyphenateStyle::new(hyphen);
+            words
+                .into_iter()
+                .map(|word| self.hyphenate_with(word, &style))
+                .collect()
+        }
+
+        /// Splits the given word into syllables at its hyphenation points.
+        ///
+        /// A character at a hyphenation point marked with `'='` by `hyphens()` is replaced by
+        /// the hyphen in hyphenated text, so it is not included in either syllable.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to split
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use voikko_rs::voikko;
+        /// # let v = voikko::Voikko::new("fi-x-morphoid", None).unwrap();
+        /// assert_eq!(v.syllables("kuorma-auto").unwrap(), vec!["kuor", "ma", "au", "to"]);
+        /// ```
+        ///
+        /// # Errors
+        ///
+        /// Returns a `VoikkoError` if `word` contains a NUL byte or libvoikko fails to hyphenate it.
+        pub fn syllables(&self, word: &str) -> Result<Vec<String>, VoikkoError> {
+            validate_input_as::<HyphenateError>(word)?;
+            let hyphens = libvoikko::hyphens(self.handle, word)?;
+            let mut syllables = Vec::new();
+            let mut syllable = String::new();
+            for (w, h) in word.graphemes(true).zip(hyphens.graphemes(true)) {
+                if h == "-" || h == "=" {
+                    syllables.push(std::mem::take(&mut syllable));
+                }
+                if h != "=" {
+                    syllable.push_str(w);
+                }
+            }
+            syllables.push(syllable);
+            Ok(syllables)
+        }
+
+        /// Hyphenates the given word in UTF-8 encoding.
+        /// Returns a string where caller-supplied characters are inserted in all hyphenation points.
+        /// **Requires libvoikko version 4.2.0 or greater.**
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to hyphenate
+        /// * `character` - string to insert at hyphenation points
+        /// * `allow_context_changes` - boolean parameter controlling whether to insert hyphens even if they alter the word
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use voikko_rs::voikko;
+        /// # let v = voikko::Voikko::new("fi-x-morphoid", None).unwrap();
+        /// // Voikko initialized on the variable v
+        /// let hyphenated1 = v.hyphenate_new("rei'ittää", "-", true);
+        /// assert_eq!(hyphenated1, Ok(String::from("rei-it-tää")));
+        /// let hyphenated2 = v.hyphenate_new("rei'ittää", "-", false);
+        /// assert_eq!(hyphenated2, Ok(String::from("rei'it-tää")));
+        ///
+        /// ```
+        ///
+        /// # Errors
+        ///
+        /// Is Err if libvoikko returns a null pointer, i.e. it fails to hyphenate.
+        pub fn hyphenate_new(&self, word: &str, character: &str, allow_context_changes: bool) -> Result<String, HyphenateError> {
+            validate_input_as::<HyphenateError>(word)?;
+            validate_input_as::<HyphenateError>(character)?;
+            libvoikko::insert_hyphens(self.handle, word, character, allow_context_changes)
+        }
+
+        /// Hyphenate given word allowing context changes, and report whether a context change
+        /// actually happened. The flag is `true` if the result differs from the conservative
+        /// hyphenation, i.e. `hyphenate_new()` with `allow_context_changes` set to `false`.
+        /// **Requires libvoikko version 4.2.0 or greater.**
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to hyphenate
+        /// * `hyphen` - string to insert at hyphenation points
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use voikko_rs::voikko;
+        /// # let v = voikko::Voikko::new("fi-x-morphoid", None).unwrap();
+        /// let (hyphenated, changed) = v.hyphenate_detecting_changes("rei'ittää", "-").unwrap();
+        /// assert_eq!(hyphenated, "rei-it-tää");
+        /// assert!(changed);
+        /// ```
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if libvoikko fails to hyphenate the word.
+        pub fn hyphenate_detecting_changes(
+            &self,
+            word: &str,
+            hyphen: &str,
+        ) -> Result<(String, bool), VoikkoError> {
+            validate_input_as::<HyphenateError>(word)?;
+            validate_input_as::<HyphenateError>(hyphen)?;
+            let hyphenated = self.hyphenate_new(word, hyphen, true)?;
+            let conservative = self.hyphenate_new(word, hyphen, false)?;
+            let changed = hyphenated != conservative;
+            Ok((hyphenated, changed))
+        }
+
+        /// Returns `true` if hyphenating the given word with an empty hyphen string reproduces
+        /// the original word, i.e. no character is replaced at a hyphenation point marked with
+        /// `'='` by `hyphens()`.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to check
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use voikko_rs::voikko;
+        /// # let v = voikko::Voikko::new("fi-x-morphoid", None).unwrap();
+        /// assert!(v.hyphenation_is_lossless("kissa").unwrap());
+        /// assert!(!v.hyphenation_is_lossless("rei'ittää").unwrap());
+        /// ```
+        ///
+        /// # Errors
+        ///
+        /// Returns a `VoikkoError` if `word` contains a NUL byte or libvoikko fails to hyphenate it.
+        pub fn hyphenation_is_lossless(&self, word: &str) -> Result<bool, VoikkoError> {
+            validate_input_as::<HyphenateError>(word)?;
+            let hyphens = libvoikko::hyphens(self.handle, word)?;
+            Ok(apply_hyphens(word, &hyphens, &HyphenateStyle::new("")) == word)
+        }
+
+        /// Finds where to break the given word at the end of a line with `max_chars` characters
+        /// of room left. Returns the head and tail of the word split at the last hyphenation
+        /// point where the head followed by a one-character hyphen fits in `max_chars`, or `None`
+        /// if there is no such point. A character replaced by the hyphen at a point marked with
+        /// `'='` by `hyphens()`, such as the hyphen in "kuorma-auto", is in neither part.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to break
+        /// * `max_chars` - room left on the line, in characters
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use voikko_rs::voikko;
+        /// # let v = voikko::Voikko::new("fi-x-morphoid", None).unwrap();
+        /// let (head, tail) = v.break_to_fit("kuorma-auto", 8).unwrap().unwrap();
+        /// assert_eq!(format!("{head}-\n{tail}"), "kuorma-\nauto");
+        /// ```
+        ///
+        /// # Errors
+        ///
+        /// Returns a `VoikkoError` if `word` contains a NUL byte or libvoikko fails to hyphenate it.
+        pub fn break_to_fit(&self, word: &str, max_chars: usize) -> Result<Option<(String, String)>, VoikkoError> {
+            validate_input_as::<HyphenateError>(word)?;
+            let hyphens = libvoikko::hyphens(self.handle, word)?;
+            let split = word
+                .char_indices()
+                .zip(hyphens.chars())
+                .enumerate()
+                // a head of i characters and the hyphen must fit
+                .filter(|&(i, (_, h))| (h == '-' || h == '=') && i < max_chars)
+                .last();
+            Ok(split.map(|(_, ((pos, c), h))| {
+                let tail = if h == '=' { pos + c.len_utf8() } else { pos };
+                (String::from(&word[..pos]), String::from(&word[tail..]))
+            }))
+        }
+
+        /// Splits a compound word into the base forms of its parts and hyphenates each part
+        /// like `hyphenate()`. The parts are read from the `WORDBASES` attribute of the first
+        /// analysis, e.g. `"+kalja(kalja)+kori(kori)"`. A word that is not a compound, or has no
+        /// analysis, gives a vector with just the hyphenated word as given, not its base form,
+        /// e.g. `["kis-san"]` for `"kissan"`.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to hyphenate
+        /// * `hyphen` - string to insert at hyphenation points
+        ///
+        /// # Errors
+        ///
+        /// Returns a `VoikkoError` if `word` contains a NUL byte or libvoikko fails to hyphenate
+        /// a part.
+        pub fn hyphenate_compound_parts(&self, word: &str, hyphen: &str) -> Result<Vec<String>, VoikkoError> {
+            validate_input_as::<HyphenateError>(word)?;
+            let style = HyphenateStyle::new(hyphen);
+            // the same NUL-terminated copy of the word is used for analysis and hyphenation
+            let word_cstring = std::ffi::CString::new(word)?;
+            let wordbases = self
+                .analyze_first_cstr(&word_cstring, Some(&["WORDBASES"]))
+                .and_then(|mut analysis| analysis.remove("WORDBASES"));
+            let parts: Vec<&str> = wordbases
+                .as_deref()
+                .unwrap_or_default()
+                .split('+')
+                .filter(|part| !part.is_empty())
+                .map(|part| match part.split_once('(') {
+                    Some((_, rest)) => rest.split_once(')').map_or(rest, |(base, _)| base),
+                    None => part,
+                })
+                .collect();
+            if parts.len() < 2 {
+                let hyphens = libvoikko::hyphens_cstr(self.handle, &word_cstring)?;
+                return Ok(vec![apply_hyphens(word, &hyphens, &style)]);
+            }
+            // the parts are copied into the buffer of the word in turn
+            let mut buffer = word_cstring.into_bytes();
+            parts
+                .into_iter()
+                .map(|part| {
+                    buffer.clear();
+                    buffer.extend_from_slice(part.as_bytes());
+                    let part_cstring = std::ffi::CString::new(std::mem::take(&mut buffer))?;
+                    let hyphens = libvoikko::hyphens_cstr(self.handle, &part_cstring);
+                    buffer = part_cstring.into_bytes();
+                    Ok(apply_hyphens(part, &hyphens?, &style))
+                })
+                .collect()
+        }
+
+        /// Tokenize a text string. Returns a vector of Token structs, or an empty vector if the
+        /// text contains a NUL byte.
+        ///
+        /// libvoikko signals the end of input with a `None` token. Should it ever return one
+        /// before the whole text has been consumed, the remaining text is returned as a single
+        /// `Unknown` token so that no input is silently dropped.
+        ///
+        /// With `set_normalize_apostrophes(true)`, typographic apostrophes are treated as straight
+        /// ones, but the token texts still contain the original characters.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to find tokens in.
+        #[must_use]
+        pub fn tokens(&self, text: &str) -> Vec<Token> {
+            if validate_input(text).is_err() {
+                return vec![];
+            }
+            self.token_refs(text).into_iter().map(Token::from).collect()
+        }
+
+        /// Tokenize a text string like `tokens()`, but without copying the token texts. Returns
+        /// a vector of `TokenRef` structs borrowing from `text`.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to find tokens in.
+        #[must_use]
+        pub fn token_refs<'t>(&self, text: &'t str) -> Vec<TokenRef<'t>> {
+            if validate_input(text).is_err() {
+                return vec![];
+            }
+            self.token_refs_consumed(text, true).0
+        }
+
+        /// Tokenize a text string like `tokens()`, and return the number of bytes of the text
+        /// that libvoikko tokenized. The text has been fully tokenized if the number equals
+        /// `text.len()`.
+        ///
+        /// Unlike `tokens()`, text after the position where libvoikko stopped is not returned as
+        /// an `Unknown` token. If the text contains a NUL byte, no tokens and 0 are returned.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to find tokens in.
+        #[must_use]
+        pub fn tokens_checked(&self, text: &str) -> (Vec<Token>, usize) {
+            if validate_input(text).is_err() {
+                return (vec![], 0);
+            }
+            let (tokens, consumed) = self.token_refs_consumed(text, false);
+            (tokens.into_iter().map(Token::from).collect(), consumed)
+        }
+
+        // Tokenizes `text` and returns the tokens and the number of bytes tokenized by libvoikko.
+        // If libvoikko stops early, the rest of the text is returned as an Unknown token when
+        // `include_rest` is set.
+        fn token_refs_consumed<'t>(&self, text: &'t str, include_rest: bool) -> (Vec<TokenRef<'t>>, usize) {
+            if self.normalize_apostrophes.get() && text.contains(TYPOGRAPHIC_APOSTROPHES) {
+                let normalized: String = text
+                    .chars()
+                    .map(|c| if TYPOGRAPHIC_APOSTROPHES.contains(&c) { '\'' } else { c })
+                    .collect();
+                // the normalized text has the same characters at the same positions, so the
+                // tokens can be mapped back to the original text by character counts
+                let mut rest = text;
+                let (tokens, normalized_consumed) = self.token_refs_consumed(&normalized, include_rest);
+                let tokens: Vec<TokenRef<'t>> = tokens
+                    .into_iter()
+                    .map(|token| {
+                        let len = char_byte_len(rest, token.token_text.chars().count());
+                        let original = TokenRef::new(&rest[..len], token.token_type);
+                        rest = &rest[len..];
+                        original
+                    })
+                    .collect();
+                let consumed = char_byte_len(text, normalized[..normalized_consumed].chars().count());
+                return (tokens, consumed);
+            }
+            let Ok(mut raw_tokens) = RawTokens::new(self, text) else {
+                return (vec![], 0);
+            };
+            let mut tokens = Vec::new();
+            let mut consumed = 0;
+            while let Some((token_text, token_type)) = raw_tokens.next() {
+                if raw_tokens.stopped {
+                    if include_rest {
+                        tokens.push(TokenRef::new(token_text, token_type));
+                    }
+                    break;
+                }
+                consumed += token_text.len();
+                tokens.push(TokenRef::new(token_text, token_type));
+            }
+            (tokens, consumed)
+        }
+
+        /// Treat typographic apostrophes (U+2019 RIGHT SINGLE QUOTATION MARK and U+02BC MODIFIER
+        /// LETTER APOSTROPHE) as straight apostrophes in `tokens()`, so that e.g. "rei’ittää" is
+        /// a single word.
+        ///
+        /// Default: false
+        pub fn set_normalize_apostrophes(&self, value: bool) {
+            self.normalize_apostrophes.set(value);
+        }
+
+        /// Tokenize a text string and check the spelling of each word. Returns a vector of Token
+        /// structs paired with the spell check result, which is `None` for tokens that are not
+        /// words.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to find tokens in.
+        #[must_use]
+        pub fn tokens_with_spelling(&self, text: &str) -> Vec<(Token, Option<SpellReturn>)> {
+            if validate_input(text).is_err() {
+                return vec![];
+            }
+            self.tokens(text)
+                .into_iter()
+                .map(|token| {
+                    let spelling = match token.token_type {
+                        TokenType::Word => Some(self.spell(&token.token_text)),
+                        _ => None,
+                    };
+                    (token, spelling)
+                })
+                .collect()
+        }
+
+        /// Tokenize a text string and return only the texts of `Word` tokens, in order.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to find words in.
+        #[must_use]
+        pub fn words(&self, text: &str) -> Vec<String> {
+            if validate_input(text).is_err() {
+                return vec![];
+            }
+            self.tokens(text)
+                .into_iter()
+                .filter(|token| token.token_type == TokenType::Word)
+                .map(|token| token.token_text)
+                .collect()
+        }
+
+        /// Returns `true` if the given text is exactly one `Word` token, with no whitespace,
+        /// punctuation or other tokens around it.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to check.
+        #[must_use]
+        pub fn is_single_word(&self, text: &str) -> bool {
+            if validate_input(text).is_err() {
+                return false;
+            }
+            match self.token_refs(text).as_slice() {
+                [token] => token.token_type == TokenType::Word,
+                _ => false,
+            }
+        }
+
+        /// Tokenize a text string like `tokens()`, with whitespace normalized. Each run of
+        /// adjacent `Whitespace` tokens, including a run of just one token, is replaced by a
+        /// single `Whitespace` token whose text is a single space. Tabs and line breaks are
+        /// collapsed too, so the tokens no longer reproduce the original text.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to find tokens in.
+        #[must_use]
+        pub fn tokens_normalized(&self, text: &str) -> Vec<Token> {
+            if validate_input(text).is_err() {
+                return vec![];
+            }
+            let mut tokens: Vec<Token> = Vec::new();
+            for token in self.token_refs(text) {
+                if token.token_type == TokenType::Whitespace {
+                    if tokens.last().is_some_and(|last| last.token_type == TokenType::Whitespace) {
+                        continue;
+                    }
+                    tokens.push(Token::new(" ", TokenType::Whitespace));
+                } else {
+                    tokens.push(token.to_token());
+                }
+            }
+            tokens
+        }
+
+        /// Tokenize text from a reader lazily. Returns an iterator of Token results.
+        ///
+        /// The input is read in chunks, so it does not need to fit in memory. Tokens that may
+        /// continue past the end of a chunk are held back until more input has been read, so the
+        /// tokens are the same as from `tokens()` on the whole text, as long as the text has no
+        /// words spanning a chunk boundary without whitespace around them. Text is held back only
+        /// up to a limit, so a very long stretch without whitespace may be split differently.
+        ///
+        /// # Arguments
+        ///
+        /// * `reader` - Reader to read UTF-8 encoded text from.
+        ///
+        /// # Errors
+        ///
+        /// The iterator yields an error and stops if reading fails, or if the input is not valid
+        /// UTF-8 or contains a NUL byte.
+        pub fn tokenize_reader<R: Read>(&self, reader: R) -> ReaderTokens<'_, R> {
+            ReaderTokens {
+                voikko: self,
+                reader,
+                buffer: Vec::new(),
+                searched: 0,
+                pending: VecDeque::new(),
+                eof: false,
+            }
+        }
+
+        /// Find sentences in a text string. Returns a vector of Sentence structs, or an empty
+        /// vector if the text contains a NUL byte.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to find sentences in.
+        #[must_use]
+        pub fn sentences(&self, text: &str) -> Vec<Sentence> {
+            if validate_input(text).is_err() {
+                return vec![];
+            }
+            let mut sentlist = Vec::new();
+            self.each_sentence(text, |sentence_text, next_start_type| {
+                sentlist.push(Sentence::new(sentence_text, next_start_type));
+            });
+            sentlist
+        }
+
+        /// Find sentences in a text string like `sentences()`, but without copying the sentence
+        /// texts. Returns a vector of `SentenceRef` structs borrowing from `text`.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to find sentences in.
+        #[must_use]
+        pub fn sentence_refs<'t>(&self, text: &'t str) -> Vec<SentenceRef<'t>> {
+            if validate_input(text).is_err() {
+                return vec![];
+            }
+            let mut sentlist = Vec::new();
+            self.each_sentence(text, |sentence_text, next_start_type| {
+                sentlist.push(SentenceRef::new(sentence_text, next_start_type));
+            });
+            sentlist
+        }
+
+        /// Find sentences in a text string, splitting only at sentence boundaries that are at
+        /// least as confident as `min`. Returns a vector of the sentence texts.
+        ///
+        /// For example with `min` set to `SentenceType::Probable`, sentences followed by a
+        /// `Possible` boundary are merged with the next sentence.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to find sentences in.
+        /// * `min` - Least confident boundary type to split at.
+        #[must_use]
+        pub fn sentences_min_confidence(&self, text: &str, min: SentenceType) -> Vec<String> {
+            if validate_input(text).is_err() {
+                return vec![];
+            }
+            let mut sentlist = Vec::new();
+            let mut current = String::new();
+            self.each_sentence(text, |sentence_text, next_start_type| {
+                current.push_str(sentence_text);
+                if next_start_type >= min {
+                    sentlist.push(std::mem::take(&mut current));
+                }
+            });
+            if !current.is_empty() {
+                sentlist.push(current);
+            }
+            sentlist
+        }
+
+        /// Count the sentences in a text string without copying them.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to count sentences in.
+        #[must_use]
+        pub fn sentence_count(&self, text: &str) -> usize {
+            if validate_input(text).is_err() {
+                return 0;
+            }
+            let mut count = 0;
+            self.each_sentence(text, |_, _| count += 1);
+            count
+        }
+
+        /// Compute word, sentence and punctuation statistics of a text string.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to compute statistics for.
+        #[allow(clippy::cast_precision_loss)]
+        #[must_use]
+        pub fn text_stats(&self, text: &str) -> TextStats {
+            if validate_input(text).is_err() {
+                return TextStats {
+                    word_count: 0,
+                    sentence_count: 0,
+                    average_sentence_length: 0.0,
+                    punctuation_count: 0,
+                };
+            }
+            let mut word_count = 0;
+            let mut punctuation_count = 0;
+            for token in self.tokens(text) {
+                match token.token_type {
+                    TokenType::Word => word_count += 1,
+                    TokenType::Punctuation => punctuation_count += 1,
+                    _ => {}
+                }
+            }
+            let sentence_count = self.sentence_count(text);
+            let average_sentence_length = if sentence_count == 0 {
+                0.0
+            } else {
+                word_count as f64 / sentence_count as f64
+            };
+            TextStats {
+                word_count,
+                sentence_count,
+                average_sentence_length,
+                punctuation_count,
+            }
+        }
+
+        /// Average number of syllables per word in a text string, approximated by hyphenating each
+        /// word: a word has one syllable more than it has hyphenation points. Returns 0 if the
+        /// text has no words.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to compute the average for.
+        ///
+        /// # Errors
+        ///
+        /// Returns a `VoikkoError` if `text` contains a NUL byte or libvoikko fails to hyphenate
+        /// a word.
+        #[allow(clippy::cast_precision_loss)]
+        pub fn avg_syllables_per_word(&self, text: &str) -> Result<f64, VoikkoError> {
+            validate_input(text)?;
+            let mut word_count: usize = 0;
+            let mut syllable_count: usize = 0;
+            for token in self.token_refs(text) {
+                if token.token_type == TokenType::Word {
+                    word_count += 1;
+                    syllable_count += self.hyphenation(token.token_text)?.points().len() + 1;
+                }
+            }
+            if word_count == 0 {
+                Ok(0.0)
+            } else {
+                Ok(syllable_count as f64 / word_count as f64)
+            }
+        }
+
+        // Calls `f` with the text slice and next start type of each sentence in `text`. Does
+        // nothing if the text contains a NUL byte.
+        #[allow(clippy::match_wildcard_for_single_variants)]
+        fn each_sentence<'t, F: FnMut(&'t str, SentenceType)>(&self, text: &'t str, mut f: F) {
+            let Ok(text_cstring) = std::ffi::CString::new(text) else {
+                return;
+            };
+            // offset is in bytes, but libvoikko returns sentence lengths in characters
+            let mut offset = 0;
+            let mut next_start_type = SentenceType::NoStart;
+            while offset < text.len() && next_start_type != SentenceType::None {
+                let next_text = &text[offset..];
+                let (raw_sent, sent_len) = libvoikko::next_sentence(self.handle, &text_cstring, offset);
+                next_start_type = match raw_sent {
+                    libvoikko::voikko_sentence_type::SENTENCE_NO_START => SentenceType::NoStart,
+                    libvoikko::voikko_sentence_type::SENTENCE_POSSIBLE => SentenceType::Possible,
+                    libvoikko::voikko_sentence_type::SENTENCE_PROBABLE => SentenceType::Probable,
+                    _ => SentenceType::None,
+                };
+                let mut sent_bytes = char_byte_len(next_text, sent_len);
+                if sent_bytes == 0 {
+                    // an empty sentence would never advance the offset
+                    sent_bytes = next_text.len();
+                    next_start_type = SentenceType::None;
+                }
+                f(&next_text[..sent_bytes], next_start_type);
+                offset += sent_bytes;
+            }
+        }
+
+        /// Find sentences in a text string. Returns a vector of the sentence texts.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to find sentences in.
+        #[must_use]
+        pub fn sentence_texts(&self, text: &str) -> Vec<String> {
+            if validate_input(text).is_err() {
+                return vec![];
+            }
+            self.sentences(text)
+                .into_iter()
+                .map(|sentence| sentence.text)
+                .collect()
+        }
+
+        /// Tokenize a text string sentence by sentence. Returns a vector with the tokens of each
+        /// sentence found by `sentences()`, or an empty vector if the text contains a NUL byte.
+        ///
+        /// Whitespace between two sentences belongs to the sentence before it, like in the
+        /// sentence texts, so each group except the last usually ends with a `Whitespace` token.
+        /// Joining all the groups gives the same tokens as `tokens()`, except that a token
+        /// spanning a sentence boundary is split in two.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to find tokens in.
+        #[must_use]
+        pub fn tokens_by_sentence(&self, text: &str) -> Vec<Vec<Token>> {
+            if validate_input(text).is_err() {
+                return vec![];
+            }
+            let mut groups = Vec::new();
+            self.each_sentence(text, |sentence_text, _| groups.push(self.tokens(sentence_text)));
+            groups
+        }
+
+        /// Analyzes the morphology of given word.
+        ///
+        /// Returns a vector of Analysis structs (`std::collections::HashMap`) or an empty vector if
+        /// analysis fails.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to analyze
+        // https://github.com/voikko/corevoikko/blob/rel-libvoikko-4.1.1/libvoikko/doc/morphological-analysis.txt
+        #[must_use]
+        pub fn analyze(&self, word: &str) -> Vec<Analysis> {
+            if validate_input(word).is_err() {
+                return vec![];
+            }
+            libvoikko::analyze_word(self.handle, word).unwrap_or_else(|_| vec![])
+        }
+
+        /// Analyzes the morphology of given word, like `analyze()`, but includes only the given
+        /// attributes in each analysis. The values of other attributes are not fetched at all.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to analyze
+        /// * `keys` - attributes to include, e.g. `&["BASEFORM", "CLASS"]`
+        #[must_use]
+        pub fn analyze_keys(&self, word: &str, keys: &[&str]) -> Vec<Analysis> {
+            if validate_input(word).is_err() {
+                return vec![];
+            }
+            libvoikko::analyze_word_filtered(self.handle, word, usize::MAX, Some(keys))
+                .unwrap_or_else(|_| vec![])
+        }
+
+        /// Analyzes the morphology of given word. Returns an iterator that converts each analysis
+        /// only when it is needed, so stopping early avoids the work for the rest.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to analyze
+        pub fn analyses<'a>(&'a self, word: &'a str) -> impl Iterator<Item = Analysis> + 'a {
+            validate_input(word)
+                .ok()
+                .and_then(|()| libvoikko::AnalysisList::new(self.handle, word).ok())
+                .into_iter()
+                .flatten()
+        }
+
+        /// Analyzes the morphology of given word and returns only the first analysis, or `None`
+        /// if there are none. The remaining analyses are not converted at all.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to analyze
+        #[must_use]
+        pub fn analyze_first(&self, word: &str) -> Option<Analysis> {
+            if validate_input(word).is_err() {
+                return None;
             }
+            let word_cstring = std::ffi::CString::new(word).ok()?;
+            self.analyze_first_cstr(&word_cstring, None)
         }
-    }
 
-    impl std::fmt::Display for HyphenateError {
-        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-            write!(f, "{}", self.message)
+        // Like analyze_first, but takes an already NUL-terminated word and includes only the
+        // given attributes if `keys` is not None. Methods that also pass the word to libvoikko
+        // otherwise can use this to convert it just once.
+        pub(crate) fn analyze_first_cstr(&self, word: &std::ffi::CStr, keys: Option<&[&str]>) -> Option<Analysis> {
+            libvoikko::analyze_word_cstr(self.handle, word, 1, keys).pop()
         }
-    }
 
-    impl error::Error for HyphenateError {
-        fn description(&self) -> &str {
-            self.message.as_str()
+        // Like analyze_first_cstr, but returns all analyses.
+        pub(crate) fn analyze_cstr(&self, word: &std::ffi::CStr, keys: Option<&[&str]>) -> Vec<Analysis> {
+            libvoikko::analyze_word_cstr(self.handle, word, usize::MAX, keys)
         }
-    }
 
-    impl std::convert::From<std::ffi::NulError> for HyphenateError {
-        fn from(error: std::ffi::NulError) -> Self {
-            HyphenateError {
-                message: format!("{}", error)
+        /// Returns the distinct base forms of given word across all of its analyses, in the order
+        /// they first appear. Returns an empty vector if analysis fails.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to find base forms for
+        #[must_use]
+        pub fn baseforms(&self, word: &str) -> Vec<String> {
+            if validate_input(word).is_err() {
+                return vec![];
+            }
+            let Ok(word_cstring) = std::ffi::CString::new(word) else {
+                return vec![];
+            };
+            let mut baseforms: Vec<String> = Vec::new();
+            for mut analysis in self.analyze_cstr(&word_cstring, Some(&["BASEFORM"])) {
+                if let Some(baseform) = analysis.remove("BASEFORM") {
+                    if !baseforms.contains(&baseform) {
+                        baseforms.push(baseform);
+                    }
+                }
             }
+            baseforms
         }
-    }
 
-    impl std::convert::From<std::str::Utf8Error> for HyphenateError {
-        fn from(error: std::str::Utf8Error) -> Self {
-            HyphenateError {
-                message: format!("{}", error)
+        /// Returns the longest base form of given word across all of its analyses, or `None` if
+        /// analysis fails. The length is counted in characters, and of equally long base forms
+        /// the first one is returned.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to find the base form for
+        #[must_use]
+        pub fn longest_baseform(&self, word: &str) -> Option<String> {
+            if validate_input(word).is_err() {
+                return None;
             }
+            self.baseforms(word)
+                .into_iter()
+                .reduce(|longest, baseform| {
+                    if baseform.chars().count() > longest.chars().count() {
+                        baseform
+                    } else {
+                        longest
+                    }
+                })
         }
-    }
 
-    impl Voikko {
-        /// Initializes Voikko and returns a `Result<Voikko, InitError>`
+        /// Returns `true` if the two words have at least one base form in common.
         ///
         /// # Arguments
         ///
-        /// * `language` - BCP 47 language tag for the language to be used.
-        ///                Private use subtags can be used to specify the dictionary variant.
-        /// * `path` - Path to a directory from which dictionary files should be searched first before
-        ///            looking into the standard dictionary locations. If `None`, no additional search path
-        ///            will be used.
+        /// * `a` - first word
+        /// * `b` - second word
+        #[must_use]
+        pub fn same_baseform(&self, a: &str, b: &str) -> bool {
+            if validate_input(a).is_err() || validate_input(b).is_err() {
+                return false;
+            }
+            let baseforms = self.baseforms(a);
+            self.baseforms(b).iter().any(|baseform| baseforms.contains(baseform))
+        }
+
+        /// Returns the distinct analysis attributes of given sample word across all of its
+        /// analyses, sorted alphabetically. Returns an empty vector if analysis fails.
         ///
-        /// # Errors
+        /// The attributes available depend on the dictionary, and in some cases on the word, so
+        /// this only tells which attributes the dictionary gives for words like the sample.
         ///
-        /// Returns an `InitError` result if init fails.
-        pub fn new(language: &str, path: Option<&str>) -> Result<Voikko, InitError> {
-            let v = libvoikko::init(language, path);
-
-            match v {
-                Ok(handle) => Ok(Voikko { handle }),
-                Err(error) => Err(error),
+        /// # Arguments
+        ///
+        /// * `sample_word` - word to analyze, e.g. a common noun such as `"kissa"`
+        #[must_use]
+        pub fn available_analysis_keys(&self, sample_word: &str) -> Vec<String> {
+            if validate_input(sample_word).is_err() {
+                return vec![];
+            }
+            let mut keys: Vec<String> = Vec::new();
+            for analysis in self.analyze(sample_word) {
+                for key in analysis.into_inner().into_keys() {
+                    if !keys.contains(&key) {
+                        keys.push(key);
+                    }
+                }
             }
+            keys.sort();
+            keys
         }
 
-        /// Check the spelling of a UTF-8 character string.
+        /// Returns a canonical lookup key for given word: the base form from its first analysis,
+        /// or the word in lowercase if it cannot be analyzed.
         ///
         /// # Arguments
         ///
-        /// * `word` - word to check
+        /// * `word` - word to find the key for
         #[must_use]
-        pub fn spell(&self, word: &str) -> SpellReturn {
-            let ret = libvoikko::spell(self.handle, word);
-            match ret {
-                Ok(code) => match code {
-                    0 => SpellReturn::SpellFailed,
-                    1 => SpellReturn::SpellOk,
-                    3 => SpellReturn::CharsetConversionFailed,
-                    _ => SpellReturn::InternalError,
-                },
-                Err(_) => SpellReturn::SpellFailed,
+        pub fn canonical_key(&self, word: &str) -> String {
+            if validate_input(word).is_err() {
+                return word.to_lowercase();
             }
-
+            std::ffi::CString::new(word)
+                .ok()
+                .and_then(|word_cstring| self.analyze_first_cstr(&word_cstring, Some(&["BASEFORM"])))
+                .and_then(|analysis| analysis.baseform().map(String::from))
+                .unwrap_or_else(|| word.to_lowercase())
         }
 
-        /// Finds suggested correct spellings for given UTF-8 encoded word.
-        /// Returns a vector of strings - an empty vector, if no suggestions.
+        /// Returns the distinct word classes of given word across all of its analyses, in the
+        /// order they first appear. Returns an empty vector if analysis fails.
         ///
         /// # Arguments
         ///
-        /// * `word` - word to find suggestions for
+        /// * `word` - word to find word classes for
         #[must_use]
-        pub fn suggest(&self, word: &str) -> Vec<String> {
-            libvoikko::suggest(self.handle, word).unwrap_or_else(|_| vec![])
+        pub fn word_classes(&self, word: &str) -> Vec<WordClass> {
+            if validate_input(word).is_err() {
+                return vec![];
+            }
+            let Ok(word_cstring) = std::ffi::CString::new(word) else {
+                return vec![];
+            };
+            let mut classes: Vec<WordClass> = Vec::new();
+            for analysis in self.analyze_cstr(&word_cstring, Some(&["CLASS"])) {
+                if let Some(class) = analysis.word_class().map(WordClass::from) {
+                    if !classes.contains(&class) {
+                        classes.push(class);
+                    }
+                }
+            }
+            classes
         }
 
-        /// Hyphenates the given word in UTF-8 encoding.
-        /// Returns a string containing the hyphenation using the following notation:
-        /// * `' '` = no hyphenation at this character,
-        /// * `'-'` = hyphenation point (character at this position
-        ///        is preserved in the hyphenated form),
-        /// * `'='` = hyphenation point (character at this position
-        ///        is replaced by the hyphen.)
+        /// Returns the inflection class tag of given word from its first analysis, or `None` if
+        /// the word has no analysis or the analysis has no such tag.
+        ///
+        /// libvoikko has no dedicated attribute for the inflectional paradigm. This reads the
+        /// `FSTOUTPUT` attribute instead and returns the value of the last `[L…]` tag, which
+        /// classifies the last part of a compound word and so determines how the whole word
+        /// inflects. For example, `"kaljakori"` ends in `[Ln]`, so its class is `"n"`.
         ///
         /// # Arguments
         ///
-        /// * `word` - word to hyphenate
+        /// * `word` - word to analyze
+        #[must_use]
+        pub fn inflection_class(&self, word: &str) -> Option<String> {
+            if validate_input(word).is_err() {
+                return None;
+            }
+            let analysis = self.analyze_first(word)?;
+            parse_fstoutput(analysis.get("FSTOUTPUT")?)
+                .into_iter()
+                .rev()
+                .filter_map(|symbol| symbol.tag.strip_prefix('L').map(String::from))
+                .find(|class| !class.is_empty())
+        }
+
+        /// Analyzes the morphology of given word.
         ///
-        /// # Errors
+        /// Same as `analyze()` but returns typed `MorphologyInfo` structs.
         ///
-        /// Returns an error result on error.
-        pub fn hyphens(&self, word: &str) -> Result<String, bool> {
-            libvoikko::hyphens(self.handle, word)
+        /// # Arguments
+        ///
+        /// * `word` - word to analyze
+        #[must_use]
+        pub fn morphology(&self, word: &str) -> Vec<MorphologyInfo> {
+            if validate_input(word).is_err() {
+                return vec![];
+            }
+            self.analyze(word).into_iter().map(MorphologyInfo::from).collect()
         }
 
-        /// Hyphenates the given word in UTF-8 encoding.
-        /// Returns a string where caller-supplied characters are inserted in all hyphenation points.
+        /// Analyzes the morphology of all words in given text.
+        ///
+        /// Returns a vector of (word, analyses) pairs, one for each word token in the text, in
+        /// order. Whitespace, punctuation and unknown tokens are skipped.
         ///
         /// # Arguments
         ///
-        /// * `word` - word to hyphenate
-        /// * `hyphen` - string to insert at hyphenation points
+        /// * `text` - text to analyze
+        #[must_use]
+        pub fn analyze_text(&self, text: &str) -> Vec<(String, Vec<Analysis>)> {
+            if validate_input(text).is_err() {
+                return vec![];
+            }
+            self.tokens(text)
+                .into_iter()
+                .filter(|token| token.token_type == TokenType::Word)
+                .map(|token| {
+                    let analyses = self.analyze(&token.token_text);
+                    (token.token_text, analyses)
+                })
+                .collect()
+        }
+
+        /// Find all grammar errors in given text.
         ///
-        /// # Errors
+        /// Returns a vector of `GrammarError` structs or an empty vector if no errors found.
+        /// The errors are sorted by `start_pos`.
         ///
-        /// Returns an error result on error.
-        pub fn hyphenate(&self, word: &str, hyphen: &str) -> Result<String, bool> {
-            let hyphens = self.hyphens(word);
-            match hyphens {
-                Err(_) => Err(false),
-                Ok(hyph) => Ok(word
-                    .graphemes(true)
-                    .zip(hyph.graphemes(true))
-                    .map(|(w, h)| match h {
-                        // " " => String::from(w),
-                        "-" => format!("{}{}", hyphen, w),
-                        "=" => String::from(hyphen),
-                        _ => String::from(w),
-                    })
-                    .collect::<String>()),
+        /// # Arguments
+        ///
+        /// * `text` - Text to find grammar errors in. The text should usually begin at the start of
+        ///            a paragraph or sentence.
+        /// * `desc_lang` - ISO language code for the language in which to recieve error descriptions.
+        #[must_use]
+        pub fn grammar_errors(&self, text: &str, desc_lang: &str) -> Vec<GrammarError> {
+            if validate_input(text).is_err() || validate_input(desc_lang).is_err() {
+                return vec![];
             }
+            libvoikko::get_grammar_errors(
+                self.handle,
+                text,
+                desc_lang,
+                &mut self.grammar_descriptions.borrow_mut(),
+            ).unwrap_or_else(|_| vec![])
         }
 
-        /// Hyphenates the given word in UTF-8 encoding.
-        /// Returns a string where caller-supplied characters are inserted in all hyphenation points.
-        /// **Requires libvoikko version 4.2.0 or greater.**
+        /// Find all grammar errors in given text, like `grammar_errors()`, but return an error
+        /// instead of an empty vector if grammar checking fails.
         ///
         /// # Arguments
         ///
-        /// * `word` - word to hyphenate
-        /// * `character` - string to insert at hyphenation points
-        /// * `allow_context_changes` - boolean parameter controlling whether to insert hyphens even if they alter the word
+        /// * `text` - Text to find grammar errors in. The text should usually begin at the start of
+        ///            a paragraph or sentence.
+        /// * `desc_lang` - ISO language code for the language in which to recieve error descriptions.
         ///
-        /// # Examples
+        /// # Errors
         ///
-        /// ```
-        /// # use voikko_rs::voikko;
-        /// # let v = voikko::Voikko::new("fi-x-morphoid", None).unwrap();
-        /// // Voikko initialized on the variable v
-        /// let hyphenated1 = v.hyphenate_new("rei'ittää", "-", true);
-        /// assert_eq!(hyphenated1, Ok(String::from("rei-it-tää")));
-        /// let hyphenated2 = v.hyphenate_new("rei'ittää", "-", false);
-        /// assert_eq!(hyphenated2, Ok(String::from("rei'it-tää")));
+        /// Returns a `VoikkoError::GrammarCheck` if `text` or `desc_lang` contains a NUL byte, or
+        /// libvoikko fails to return a valid UTF-8 error description.
+        pub fn try_grammar_errors(&self, text: &str, desc_lang: &str) -> Result<Vec<GrammarError>, VoikkoError> {
+            validate_input_as::<GrammarCheckError>(text)?;
+            validate_input_as::<GrammarCheckError>(desc_lang)?;
+            Ok(libvoikko::get_grammar_errors(
+                self.handle,
+                text,
+                desc_lang,
+                &mut self.grammar_descriptions.borrow_mut(),
+            )?)
+        }
+
+        /// Find at most `max_errors` grammar errors in given text.
         ///
-        /// ```
+        /// Grammar checking stops as soon as `max_errors` errors have been found, so this is a
+        /// cheap way to bound the work done on long or unusual input.
         ///
-        /// # Errors
+        /// # Arguments
         ///
-        /// Is Err if libvoikko returns a null pointer, i.e. it fails to hyphenate.
-        pub fn hyphenate_new(&self, word: &str, character: &str, allow_context_changes: bool) -> Result<String, HyphenateError> {
-            libvoikko::insert_hyphens(self.handle, word, character, allow_context_changes)
+        /// * `text` - Text to find grammar errors in. The text should usually begin at the start of
+        ///            a paragraph or sentence.
+        /// * `desc_lang` - ISO language code for the language in which to recieve error descriptions.
+        /// * `max_errors` - Maximum number of errors to return.
+        #[must_use]
+        pub fn grammar_errors_limited(
+            &self,
+            text: &str,
+            desc_lang: &str,
+            max_errors: usize,
+        ) -> Vec<GrammarError> {
+            if validate_input(text).is_err() || validate_input(desc_lang).is_err() {
+                return vec![];
+            }
+            libvoikko::get_grammar_errors_limit(
+                self.handle,
+                text,
+                desc_lang,
+                max_errors,
+                &mut self.grammar_descriptions.borrow_mut(),
+            )
+                .unwrap_or_else(|_| vec![])
         }
 
-        /// Tokenize a text string. Returns a vector of Token structs.
+        /// Find all grammar errors in given text, like `grammar_errors()`, except errors with
+        /// one of the given codes.
         ///
         /// # Arguments
         ///
-        /// * `text` - Text to find tokens in.
-        #[allow(clippy::match_wildcard_for_single_variants)]
+        /// * `text` - Text to find grammar errors in. The text should usually begin at the start of
+        ///            a paragraph or sentence.
+        /// * `desc_lang` - ISO language code for the language in which to recieve error descriptions.
+        /// * `ignore_codes` - Error codes to leave out, e.g. `&[8]` for repeated words.
         #[must_use]
-        pub fn tokens(&self, text: &str) -> Vec<Token> {
-            let mut tokenlist = Vec::new();
-            let mut offset = 0;
-            while offset < text.len() {
-                let (raw_token, token_len) = libvoikko::next_token(self.handle, &text[offset..]);
-                let token_type = match raw_token {
-                    libvoikko::voikko_token_type::TOKEN_NONE => TokenType::None,
-                    libvoikko::voikko_token_type::TOKEN_PUNCTUATION => TokenType::Punctuation,
-                    libvoikko::voikko_token_type::TOKEN_WHITESPACE => TokenType::Whitespace,
-                    libvoikko::voikko_token_type::TOKEN_WORD => TokenType::Word,
-                    _ => TokenType::Unknown,
-                };
-                if token_type == TokenType::None {
-                    break;
-                }
-                let token_text: String = text[offset..].chars().take(token_len).collect();
-                let token = Token::new(&token_text, token_type);
-                tokenlist.push(token);
-                offset += token_text.as_bytes().len();
+        pub fn grammar_errors_filtered(
+            &self,
+            text: &str,
+            desc_lang: &str,
+            ignore_codes: &[i32],
+        ) -> Vec<GrammarError> {
+            if validate_input(text).is_err() || validate_input(desc_lang).is_err() {
+                return vec![];
             }
-            tokenlist
+            let mut errors = self.grammar_errors(text, desc_lang);
+            errors.retain(|error| !ignore_codes.contains(&error.code));
+            errors
         }
 
-        /// Find sentences in a text string. Returns a vector of Sentence structs.
+        /// Find all grammar errors in given text, with descriptions in the language set with
+        /// `set_default_grammar_desc_lang()`.
         ///
         /// # Arguments
         ///
-        /// * `text` - Text to find sentences in.
-        #[allow(clippy::match_wildcard_for_single_variants)]
+        /// * `text` - Text to find grammar errors in. The text should usually begin at the start of
+        ///            a paragraph or sentence.
         #[must_use]
-        pub fn sentences(&self, text: &str) -> Vec<Sentence> {
-            let mut sentlist = Vec::new();
-            let mut offset = 0;
-            let mut next_start_type = SentenceType::NoStart;
-            while offset < text.chars().count() && next_start_type != SentenceType::None {
-                // sent_len is in UTF-8 characters, not bytes
-                let next_text = text.chars().skip(offset).collect::<String>();
-                let (raw_sent, sent_len) =
-                    libvoikko::next_sentence(self.handle, next_text.as_str());
-                next_start_type = match raw_sent {
-                    libvoikko::voikko_sentence_type::SENTENCE_NO_START => SentenceType::NoStart,
-                    libvoikko::voikko_sentence_type::SENTENCE_POSSIBLE => SentenceType::Possible,
-                    libvoikko::voikko_sentence_type::SENTENCE_PROBABLE => SentenceType::Probable,
-                    _ => SentenceType::None,
-                };
-                // construct new Sentence object with text slice and sentence type
-                let token = Sentence::new(
-                    text.chars()
-                        .skip(offset)
-                        .take(sent_len)
-                        .collect::<String>()
-                        .as_str(),
-                    next_start_type,
-                );
-                sentlist.push(token);
-                offset += sent_len;
+        pub fn grammar_errors_default(&self, text: &str) -> Vec<GrammarError> {
+            if validate_input(text).is_err() {
+                return vec![];
             }
-            sentlist
+            self.grammar_errors(text, &self.default_desc_lang.borrow())
         }
 
-        /// Analyzes the morphology of given word.
+        /// Set the language of error descriptions returned by `grammar_errors_default()`.
         ///
-        /// Returns a vector of Analysis structs (`std::collections::HashMap`) or an empty vector if
-        /// analysis fails.
+        /// Default: "en"
         ///
         /// # Arguments
         ///
-        /// * `word` - word to analyze
-        // https://github.com/voikko/corevoikko/blob/rel-libvoikko-4.1.1/libvoikko/doc/morphological-analysis.txt
+        /// * `desc_lang` - ISO language code for the language in which to recieve error descriptions.
+        pub fn set_default_grammar_desc_lang(&self, desc_lang: &str) {
+            self.default_desc_lang.replace(String::from(desc_lang));
+        }
+
+        /// Find all grammar errors in given text, checking each paragraph separately.
+        ///
+        /// Paragraphs are separated by blank lines (lines containing only whitespace). Error
+        /// positions are in characters from the start of the whole `text`.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to find grammar errors in.
+        /// * `desc_lang` - ISO language code for the language in which to recieve error descriptions.
         #[must_use]
-        pub fn analyze(&self, word: &str) -> Vec<Analysis> {
-            libvoikko::analyze_word(self.handle, word).unwrap_or_else(|_| vec![])
+        pub fn grammar_errors_by_paragraph(&self, text: &str, desc_lang: &str) -> Vec<GrammarError> {
+            if validate_input(text).is_err() || validate_input(desc_lang).is_err() {
+                return vec![];
+            }
+            let mut errors = Vec::new();
+            let mut check = |start: usize, end: usize| {
+                let paragraph = text[start..end].trim_end();
+                if paragraph.is_empty() {
+                    return;
+                }
+                let char_offset = text[..start].chars().count();
+                for mut error in self.grammar_errors(paragraph, desc_lang) {
+                    error.start_pos += char_offset;
+                    errors.push(error);
+                }
+            };
+            // byte offset where the current paragraph starts
+            let mut start = 0;
+            let mut line_start = 0;
+            for line in text.split_inclusive('\n') {
+                let line_end = line_start + line.len();
+                if line.trim().is_empty() {
+                    check(start, line_start);
+                    start = line_end;
+                }
+                line_start = line_end;
+            }
+            check(start, text.len());
+            errors
         }
 
-        /// Find all grammar errors in given text.
+        /// Check whether given text contains any grammar errors.
         ///
-        /// Returns a vector of `GrammarError` structs or an empty vector if no errors found.
+        /// Stops at the first error found, so this is cheaper than `grammar_errors()` when the
+        /// errors themselves are not needed. Returns `false` if `text` contains a NUL byte.
         ///
         /// # Arguments
         ///
         /// * `text` - Text to find grammar errors in. The text should usually begin at the start of
         ///            a paragraph or sentence.
-        /// * `desc_lang` - ISO language code for the language in which to recieve error descriptions.
         #[must_use]
-        pub fn grammar_errors(&self, text: &str, desc_lang: &str) -> Vec<GrammarError> {
-            libvoikko::get_grammar_errors(self.handle, text, desc_lang).unwrap_or_else(|_| vec![])
+        pub fn has_grammar_errors(&self, text: &str) -> bool {
+            if validate_input(text).is_err() {
+                return false;
+            }
+            libvoikko::has_grammar_error(self.handle, text).unwrap_or(false)
+        }
+
+        /// Check whether given text is probably an incomplete sentence. Returns `true` if the
+        /// grammar checker reports missing terminating punctuation (code 9) or a missing main
+        /// verb (code 17) when incomplete sentences in titles and at the end of paragraphs are
+        /// not accepted.
+        ///
+        /// The options `accept_titles_in_gc` and `accept_unfinished_paragraphs_in_gc` are turned
+        /// off for the check and restored afterwards.
+        ///
+        /// # Arguments
+        ///
+        /// * `text` - Text to check.
+        #[must_use]
+        pub fn is_probable_fragment(&self, text: &str) -> bool {
+            if validate_input(text).is_err() {
+                return false;
+            }
+            let previous = self.options();
+            self.set_opt_accept_titles_in_gc(false);
+            self.set_opt_accept_unfinished_paragraphs_in_gc(false);
+            // only the error codes are needed, so no descriptions are fetched
+            let codes = libvoikko::get_grammar_error_codes(self.handle, text).unwrap_or_default();
+            self.set_opt_accept_titles_in_gc(previous.accept_titles_in_gc);
+            self.set_opt_accept_unfinished_paragraphs_in_gc(previous.accept_unfinished_paragraphs_in_gc);
+            codes.iter().any(|&code| code == 9 || code == 17)
+        }
+
+        /// Returns the current values of all options of this instance.
+        #[must_use]
+        pub fn options(&self) -> VoikkoOptions {
+            self.options.get()
+        }
+
+        /// Sets all options of this instance to the values in `opts`. Returns `true` if every
+        /// option was set successfully.
+        ///
+        /// # Arguments
+        ///
+        /// * `opts` - option values to apply, e.g. ones saved earlier with `Voikko::options()`
+        pub fn apply_options(&self, opts: &VoikkoOptions) -> bool {
+            let results = [
+                self.set_opt_ignore_dot(opts.ignore_dot),
+                self.set_opt_ignore_numbers(opts.ignore_numbers),
+                self.set_opt_ignore_uppercase(opts.ignore_uppercase),
+                self.set_opt_accept_first_uppercase(opts.accept_first_uppercase),
+                self.set_opt_accept_all_uppercase(opts.accept_all_uppercase),
+                self.set_opt_no_ugly_hyphenation(opts.no_ugly_hyphenation),
+                self.set_opt_ocr_suggestions(opts.ocr_suggestions),
+                self.set_opt_ignore_nonwords(opts.ignore_nonwords),
+                self.set_opt_accept_extra_hyphens(opts.accept_extra_hyphens),
+                self.set_opt_accept_missing_hyphens(opts.accept_missing_hyphens),
+                self.set_opt_accept_titles_in_gc(opts.accept_titles_in_gc),
+                self.set_opt_accept_unfinished_paragraphs_in_gc(
+                    opts.accept_unfinished_paragraphs_in_gc,
+                ),
+                self.set_opt_hyphenate_unknown_words(opts.hyphenate_unknown_words),
+                self.set_opt_accept_bulleted_lists_in_gc(opts.accept_bulleted_lists_in_gc),
+                self.set_min_hyphenated_word_length(opts.min_hyphenated_word_length),
+                self.set_speller_cache_size(opts.speller_cache_size),
+            ];
+            results.iter().all(|&ok| ok)
+        }
+
+        fn update_options<F: FnOnce(&mut VoikkoOptions)>(&self, f: F) {
+            let mut opts = self.options.get();
+            f(&mut opts);
+            self.options.set(opts);
         }
 
         // Values of option constants documented in
@@ -553,14 +3579,22 @@ pub mod voikko {
         ///
         /// Default: false
         pub fn set_opt_ignore_dot(&self, value: bool) -> bool {
-            libvoikko::set_bool_option(self.handle, 0, value)
+            let ok = libvoikko::set_bool_option(self.handle, 0, value);
+            if ok {
+                self.update_options(|o| o.ignore_dot = value);
+            }
+            ok
         }
 
         /// (Spell checking only) Ignore words containing numbers
         ///
         /// Default: false
         pub fn set_opt_ignore_numbers(&self, value: bool) -> bool {
-            libvoikko::set_bool_option(self.handle, 1, value)
+            let ok = libvoikko::set_bool_option(self.handle, 1, value);
+            if ok {
+                self.update_options(|o| o.ignore_numbers = value);
+            }
+            ok
         }
 
         /// Accept words that are written completely in uppercase letters without checking
@@ -568,14 +3602,22 @@ pub mod voikko {
         ///
         /// Default: false
         pub fn set_opt_ignore_uppercase(&self, value: bool) -> bool {
-            libvoikko::set_bool_option(self.handle, 3, value)
+            let ok = libvoikko::set_bool_option(self.handle, 3, value);
+            if ok {
+                self.update_options(|o| o.ignore_uppercase = value);
+            }
+            ok
         }
 
         /// Accept words even when the first letter is in uppercase (start of sentence etc.)
         ///
         /// Default: true
         pub fn set_opt_accept_first_uppercase(&self, value: bool) -> bool {
-            libvoikko::set_bool_option(self.handle, 6, value)
+            let ok = libvoikko::set_bool_option(self.handle, 6, value);
+            if ok {
+                self.update_options(|o| o.accept_first_uppercase = value);
+            }
+            ok
         }
 
         /// Accept words even when all of the letters are in uppercase. Note that this is
@@ -584,14 +3626,22 @@ pub mod voikko {
         ///
         /// Default: true
         pub fn set_opt_accept_all_uppercase(&self, value: bool) -> bool {
-            libvoikko::set_bool_option(self.handle, 7, value)
+            let ok = libvoikko::set_bool_option(self.handle, 7, value);
+            if ok {
+                self.update_options(|o| o.accept_all_uppercase = value);
+            }
+            ok
         }
 
         /// Do not insert hyphenation positions that are considered to be ugly but correct
         ///
         /// Default: false
         pub fn set_opt_no_ugly_hyphenation(&self, value: bool) -> bool {
-            libvoikko::set_bool_option(self.handle, 4, value)
+            let ok = libvoikko::set_bool_option(self.handle, 4, value);
+            if ok {
+                self.update_options(|o| o.no_ugly_hyphenation = value);
+            }
+            ok
         }
 
         /// Use suggestions optimized for optical character recognition software.
@@ -599,14 +3649,22 @@ pub mod voikko {
         ///
         /// Default: false
         pub fn set_opt_ocr_suggestions(&self, value: bool) -> bool {
-            libvoikko::set_bool_option(self.handle, 8, value)
+            let ok = libvoikko::set_bool_option(self.handle, 8, value);
+            if ok {
+                self.update_options(|o| o.ocr_suggestions = value);
+            }
+            ok
         }
 
         /// (Spell checking only): Ignore non-words such as URLs and email addresses.
         ///
         /// Default: true
         pub fn set_opt_ignore_nonwords(&self, value: bool) -> bool {
-            libvoikko::set_bool_option(self.handle, 10, value)
+            let ok = libvoikko::set_bool_option(self.handle, 10, value);
+            if ok {
+                self.update_options(|o| o.ignore_nonwords = value);
+            }
+            ok
         }
 
         /// (Spell checking only): Allow some extra hyphens in words. This option relaxes
@@ -616,7 +3674,11 @@ pub mod voikko {
         ///
         /// Default: false */
         pub fn set_opt_accept_extra_hyphens(&self, value: bool) -> bool {
-            libvoikko::set_bool_option(self.handle, 11, value)
+            let ok = libvoikko::set_bool_option(self.handle, 11, value);
+            if ok {
+                self.update_options(|o| o.accept_extra_hyphens = value);
+            }
+            ok
         }
 
         /// (Spell checking only): Accept missing hyphens at the start and end of the word.
@@ -627,7 +3689,11 @@ pub mod voikko {
         ///
         /// Default: false
         pub fn set_opt_accept_missing_hyphens(&self, value: bool) -> bool {
-            libvoikko::set_bool_option(self.handle, 12, value)
+            let ok = libvoikko::set_bool_option(self.handle, 12, value);
+            if ok {
+                self.update_options(|o| o.accept_missing_hyphens = value);
+            }
+            ok
         }
 
         /// (Grammar checking only): Accept incomplete sentences that could occur in
@@ -637,7 +3703,11 @@ pub mod voikko {
         ///
         /// Default: false
         pub fn set_opt_accept_titles_in_gc(&self, value: bool) -> bool {
-            libvoikko::set_bool_option(self.handle, 13, value)
+            let ok = libvoikko::set_bool_option(self.handle, 13, value);
+            if ok {
+                self.update_options(|o| o.accept_titles_in_gc = value);
+            }
+            ok
         }
 
         /// (Grammar checking only): Accept incomplete sentences at the end of the
@@ -645,14 +3715,22 @@ pub mod voikko {
         ///
         /// Default: false
         pub fn set_opt_accept_unfinished_paragraphs_in_gc(&self, value: bool) -> bool {
-            libvoikko::set_bool_option(self.handle, 14, value)
+            let ok = libvoikko::set_bool_option(self.handle, 14, value);
+            if ok {
+                self.update_options(|o| o.accept_unfinished_paragraphs_in_gc = value);
+            }
+            ok
         }
 
         /// (Hyphenation only): Hyphenate unknown words.
         ///
         /// Default: true
         pub fn set_opt_hyphenate_unknown_words(&self, value: bool) -> bool {
-            libvoikko::set_bool_option(self.handle, 15, value)
+            let ok = libvoikko::set_bool_option(self.handle, 15, value);
+            if ok {
+                self.update_options(|o| o.hyphenate_unknown_words = value);
+            }
+            ok
         }
 
         /// (Grammar checking only): Accept paragraphs if they would be valid within
@@ -660,7 +3738,11 @@ pub mod voikko {
         ///
         /// Default: false
         pub fn set_opt_accept_bulleted_lists_in_gc(&self, value: bool) -> bool {
-            libvoikko::set_bool_option(self.handle, 16, value)
+            let ok = libvoikko::set_bool_option(self.handle, 16, value);
+            if ok {
+                self.update_options(|o| o.accept_bulleted_lists_in_gc = value);
+            }
+            ok
         }
 
         // Integer options
@@ -670,7 +3752,11 @@ pub mod voikko {
         ///
         /// Default: 2
         pub fn set_min_hyphenated_word_length(&self, value: i32) -> bool {
-            libvoikko::set_int_option(self.handle, 9, value)
+            let ok = libvoikko::set_int_option(self.handle, 9, value);
+            if ok {
+                self.update_options(|o| o.min_hyphenated_word_length = value);
+            }
+            ok
         }
 
         /// Size of the spell checker cache. This can be -1 (no cache) or
@@ -678,7 +3764,28 @@ pub mod voikko {
         ///
         /// Default: 0
         pub fn set_speller_cache_size(&self, value: i32) -> bool {
-            libvoikko::set_int_option(self.handle, 17, value)
+            let ok = libvoikko::set_int_option(self.handle, 17, value);
+            if ok {
+                self.update_options(|o| o.speller_cache_size = value);
+            }
+            ok
+        }
+
+        /// Size of the spell checker cache as an approximate budget in bytes. The cache size is
+        /// set to the valid size nearest to `approx_bytes`, i.e.
+        /// `2^cache_size * (6544*sizeof(wchar_t) + 1008)` bytes for some `cache_size >= 0`.
+        /// With a 4-byte `wchar_t`, cache size 0 is 27184 bytes and each step doubles it.
+        ///
+        /// # Arguments
+        ///
+        /// * `approx_bytes` - approximate cache size in bytes
+        pub fn set_speller_cache_bytes(&self, approx_bytes: usize) -> bool {
+            self.set_speller_cache_size(speller_cache_exponent(approx_bytes))
+        }
+
+        /// Disable the spell checker cache. Same as `set_speller_cache_size(-1)`.
+        pub fn set_speller_cache_disabled(&self) -> bool {
+            self.set_speller_cache_size(-1)
         }
     }
 
@@ -687,4 +3794,54 @@ pub mod voikko {
             libvoikko::terminate(self.handle);
         }
     }
+
+    /// Spell checker that tries several Voikko instances in order of preference
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use voikko_rs::voikko;
+    /// let morphoid = voikko::Voikko::new("fi-x-morphoid", None).unwrap();
+    /// let default = voikko::Voikko::new("fi", None).unwrap();
+    /// let multi = voikko::MultiVoikko::new(vec![morphoid, default]);
+    /// assert_eq!(multi.spell("kuningas"), (voikko::SpellReturn::SpellOk, 0));
+    /// ```
+    pub struct MultiVoikko {
+        voikkos: Vec<Voikko>,
+    }
+
+    impl MultiVoikko {
+        /// Construct new `MultiVoikko` from Voikko instances, most preferred first.
+        #[must_use]
+        pub fn new(voikkos: Vec<Voikko>) -> MultiVoikko {
+            MultiVoikko { voikkos }
+        }
+
+        /// The Voikko instances, most preferred first.
+        #[must_use]
+        pub fn voikkos(&self) -> &[Voikko] {
+            &self.voikkos
+        }
+
+        /// Check the spelling of a UTF-8 character string with each instance in order. Returns
+        /// `SpellOk` and the index of the first instance that accepts the word. If none does,
+        /// returns the result of the first instance and index 0, or `SpellFailed` if there are
+        /// no instances.
+        ///
+        /// # Arguments
+        ///
+        /// * `word` - word to check
+        #[must_use]
+        pub fn spell(&self, word: &str) -> (SpellReturn, usize) {
+            let mut first = None;
+            for (i, voikko) in self.voikkos.iter().enumerate() {
+                let result = voikko.spell(word);
+                if result == SpellReturn::SpellOk {
+                    return (result, i);
+                }
+                first.get_or_insert(result);
+            }
+            (first.unwrap_or(SpellReturn::SpellFailed), 0)
+        }
+    }
 }
@@ -18,8 +18,10 @@
 
 use crate::voikko;
 use libc::{c_char, c_int, size_t};
+use std::collections::HashMap;
 use std::ffi;
 
+/// Opaque libvoikko instance handle (`struct VoikkoHandle` in libvoikko's C API)
 #[repr(C)]
 pub struct VoikkoHandle {
     _private: [u8; 0],
@@ -166,32 +168,38 @@ extern "C" {
 }
 
 pub fn init(language: &str, path: Option<&str>) -> Result<*mut VoikkoHandle, voikko::InitError> {
-    let path_ptr: *const c_char = match path {
-        Some(x) => {
-            let tmp_cstring = ffi::CString::new(x)?;
-            tmp_cstring.as_ptr()
-        },
+    let lang = ffi::CString::new(language)?;
+    // the path CString has to live until voikkoInit returns
+    let path_cstring = path.map(ffi::CString::new).transpose()?;
+    let path_ptr: *const c_char = match &path_cstring {
+        Some(x) => x.as_ptr(),
         None => std::ptr::null::<c_char>(),
     };
-    let handle_ptr;
-    let error_ptr: *mut *const c_char = &mut std::ptr::null();
-    unsafe {
-        let lang = ffi::CString::new(language)?;
-        let lang_ptr: *const c_char = lang.as_ptr().cast::<c_char>();
-        handle_ptr = voikkoInit(error_ptr, lang_ptr, path_ptr);
-    }
+    let mut error: *const c_char = std::ptr::null();
+    let handle_ptr =
+        unsafe { voikkoInit(std::ptr::addr_of_mut!(error), lang.as_ptr(), path_ptr) };
 
     if handle_ptr.is_null() {
-        let error = unsafe { ffi::CStr::from_ptr(*error_ptr).to_str().unwrap_or_default() };
-        Err(voikko::InitError::new(error))
+        // libvoikko should always set the error message, but don't trust it blindly
+        let message = if error.is_null() {
+            None
+        } else {
+            unsafe { ffi::CStr::from_ptr(error).to_str().ok() }
+        };
+        Err(voikko::InitError::new(
+            message.unwrap_or("unknown initialization error"),
+        ))
     } else {
         Ok(handle_ptr)
     }
 }
 
+// Does nothing for a null handle, e.g. one already released by Voikko::close or into_raw.
 pub fn terminate(handle: *mut VoikkoHandle) {
-    unsafe {
-        voikkoTerminate(handle);
+    if !handle.is_null() {
+        unsafe {
+            voikkoTerminate(handle);
+        }
     }
 }
 
@@ -204,33 +212,41 @@ pub fn version<'a>() -> &'a str {
 
 pub fn spell(handle: *mut VoikkoHandle, word: &str) -> Result<isize, ffi::NulError> {
     let word_cstring = ffi::CString::new(word)?;
-    let res = unsafe { voikkoSpellCstr(handle, word_cstring.as_ptr()) };
-    Ok(res as isize)
+    Ok(spell_cstr(handle, &word_cstring))
+}
+
+pub fn spell_cstr(handle: *mut VoikkoHandle, word: &ffi::CStr) -> isize {
+    let res = unsafe { voikkoSpellCstr(handle, word.as_ptr()) };
+    res as isize
 }
 
 pub fn suggest(handle: *mut VoikkoHandle, word: &str) -> Result<Vec<String>, ffi::NulError> {
+    suggest_limit(handle, word, usize::MAX)
+}
+
+pub fn suggest_limit(handle: *mut VoikkoHandle, word: &str, max: usize) -> Result<Vec<String>, ffi::NulError> {
     let word_cstring = ffi::CString::new(word)?;
     let ptr: *mut *mut c_char = unsafe { voikkoSuggestCstr(handle, word_cstring.as_ptr()) };
-    Ok(get_string_vec(ptr, true))
+    Ok(get_string_vec_limit(ptr, true, max))
 }
 
-pub fn hyphens(handle: *mut VoikkoHandle, word: &str) -> Result<String, bool> {
-    let word_cstring = ffi::CString::new(word);
-    match word_cstring {
-        Err(_) => Err(false),
-        Ok(wcst) => {
-            let ptr = unsafe { voikkoHyphenateCstr(handle, wcst.as_ptr()) };
-            if ptr.is_null() {
-                Err(false)
-            } else {
-                let cstr = unsafe { ffi::CStr::from_ptr(ptr).to_str().unwrap() };
-                let ret = cstr.to_string();
-                unsafe {
-                    voikkoFreeCstr(ptr);
-                }
-                Ok(ret)
-            }
+pub fn hyphens(handle: *mut VoikkoHandle, word: &str) -> Result<String, voikko::HyphenateError> {
+    let word_cstring = ffi::CString::new(word)?;
+    hyphens_cstr(handle, &word_cstring)
+}
+
+// Like hyphens, but takes an already NUL-terminated word.
+pub fn hyphens_cstr(handle: *mut VoikkoHandle, word: &ffi::CStr) -> Result<String, voikko::HyphenateError> {
+    let ptr = unsafe { voikkoHyphenateCstr(handle, word.as_ptr()) };
+    if ptr.is_null() {
+        Err(voikko::HyphenateError::new("Error hyphenating string: null pointer from libvoikko"))
+    } else {
+        let cstr = unsafe { ffi::CStr::from_ptr(ptr).to_str() };
+        let ret = cstr.map(str::to_string);
+        unsafe {
+            voikkoFreeCstr(ptr);
         }
+        Ok(ret?)
     }
 }
 
@@ -255,34 +271,79 @@ pub fn insert_hyphens(handle: *mut VoikkoHandle, word: &str, hyphen: &str, allow
     }
 }
 
-pub fn next_token(handle: *mut VoikkoHandle, text: &str) -> (voikko_token_type, usize) {
-    let mut tokenlen = 0;
-    let tokenlen_ptr: *mut size_t = &mut tokenlen;
-    let token;
-    unsafe {
-        let text_cstr = ffi::CString::new(text).unwrap();
-        let text_ptr = text_cstr.as_ptr();
-        token = voikkoNextTokenCstr(handle, text_ptr, text.len(), tokenlen_ptr);
-        tokenlen = std::ptr::read_unaligned(tokenlen_ptr) as usize;
-    }
-
-    (token, tokenlen)
+// Number of bytes given to libvoikko at a time when looking for the next token or sentence.
+// libvoikko converts all of the text it is given to UCS-4 on every call, so passing the whole
+// rest of a long text would make tokenizing it quadratic.
+const SCAN_WINDOW: usize = 4096;
+
+// A token or sentence ending closer than this many bytes to the end of the window may depend
+// on text after the window, so it is looked up again with a larger window.
+const SCAN_WINDOW_MARGIN: usize = 64;
+
+// Length in bytes of the first 'chars' characters of UTF-8 'bytes'
+fn utf8_prefix_len(bytes: &[u8], chars: usize) -> usize {
+    bytes
+        .iter()
+        .enumerate()
+        .filter(|(_, &byte)| byte & 0xC0 != 0x80)
+        .nth(chars)
+        .map_or(bytes.len(), |(i, _)| i)
 }
 
-// 'text' is a pointer to the start of our buffer, in terms of bytes.
-// however, the return value 'sentlen' is a unicode character count. tricky.
-pub fn next_sentence(handle: *mut VoikkoHandle, text: &str) -> (voikko_sentence_type, usize) {
-    let mut sentlen = 0;
-    let sentlen_ptr: *mut size_t = &mut sentlen;
-    let sentence;
-    unsafe {
-        let text_cstr = ffi::CString::new(text).unwrap();
-        let text_ptr = text_cstr.as_ptr();
-        sentence = voikkoNextSentenceStartCstr(handle, text_ptr, text.len(), sentlen_ptr);
-        sentlen = std::ptr::read_unaligned(sentlen_ptr) as usize;
+// Calls 'scan' with a pointer to 'text' at 'offset' and a length in bytes, starting with a
+// window of SCAN_WINDOW bytes and doubling it until the result, a character count returned by
+// 'scan', ends clearly before the end of the window or the window covers the rest of the text.
+fn scan_windowed<T, F>(text: &ffi::CString, offset: usize, mut scan: F) -> (T, usize)
+where
+    F: FnMut(*const c_char, usize) -> (T, usize),
+{
+    // CString::as_bytes, unlike CStr::to_bytes, does not need to look for the NUL terminator
+    let rest = &text.as_bytes()[offset..];
+    let text_ptr = unsafe { text.as_ptr().add(offset) };
+    let mut window = SCAN_WINDOW;
+    loop {
+        let mut len = window.min(rest.len());
+        // cut the window at a character boundary
+        while len < rest.len() && rest[len] & 0xC0 == 0x80 {
+            len -= 1;
+        }
+        let (result, chars) = scan(text_ptr, len);
+        if len == rest.len() || utf8_prefix_len(&rest[..len], chars) + SCAN_WINDOW_MARGIN <= len {
+            return (result, chars);
+        }
+        window *= 2;
     }
+}
 
-    (sentence, sentlen)
+// 'text' is the whole input buffer and 'offset' the position in bytes to continue from, so
+// the buffer does not need to be copied for every token. the returned 'tokenlen' is a unicode
+// character count.
+pub fn next_token(handle: *mut VoikkoHandle, text: &ffi::CString, offset: usize) -> (voikko_token_type, usize) {
+    scan_windowed(text, offset, |text_ptr, len| {
+        let mut tokenlen = 0;
+        let tokenlen_ptr: *mut size_t = std::ptr::addr_of_mut!(tokenlen);
+        let token;
+        unsafe {
+            token = voikkoNextTokenCstr(handle, text_ptr, len, tokenlen_ptr);
+            tokenlen = std::ptr::read_unaligned(tokenlen_ptr) as usize;
+        }
+        (token, tokenlen)
+    })
+}
+
+// 'text' and 'offset' are as in next_token: 'offset' is in bytes, but the return value
+// 'sentlen' is a unicode character count. tricky.
+pub fn next_sentence(handle: *mut VoikkoHandle, text: &ffi::CString, offset: usize) -> (voikko_sentence_type, usize) {
+    scan_windowed(text, offset, |text_ptr, len| {
+        let mut sentlen = 0;
+        let sentlen_ptr: *mut size_t = std::ptr::addr_of_mut!(sentlen);
+        let sentence;
+        unsafe {
+            sentence = voikkoNextSentenceStartCstr(handle, text_ptr, len, sentlen_ptr);
+            sentlen = std::ptr::read_unaligned(sentlen_ptr) as usize;
+        }
+        (sentence, sentlen)
+    })
 }
 
 pub fn list_dicts(path: &str) -> Result<Vec<voikko::Dictionary>, ffi::NulError> {
@@ -300,16 +361,16 @@ pub fn list_dicts(path: &str) -> Result<Vec<voikko::Dictionary>, ffi::NulError>
                 let variant_ptr = voikko_dict_variant(*ptr.offset(i));
                 let desc_ptr = voikko_dict_description(*ptr.offset(i));
 
-                let lang_str = ffi::CStr::from_ptr(lang_ptr).to_str().unwrap();
-                let script_str = ffi::CStr::from_ptr(script_ptr).to_str().unwrap();
-                let variant_str = ffi::CStr::from_ptr(variant_ptr).to_str().unwrap();
-                let desc_str = ffi::CStr::from_ptr(desc_ptr).to_str().unwrap();
+                let lang_str = ffi::CStr::from_ptr(lang_ptr).to_string_lossy();
+                let script_str = ffi::CStr::from_ptr(script_ptr).to_string_lossy();
+                let variant_str = ffi::CStr::from_ptr(variant_ptr).to_string_lossy();
+                let desc_str = ffi::CStr::from_ptr(desc_ptr).to_string_lossy();
 
                 vect.push(voikko::Dictionary::new(
-                    lang_str,
-                    script_str,
-                    variant_str,
-                    desc_str,
+                    &lang_str,
+                    &script_str,
+                    &variant_str,
+                    &desc_str,
                 ));
                 i += 1;
             }
@@ -321,17 +382,24 @@ pub fn list_dicts(path: &str) -> Result<Vec<voikko::Dictionary>, ffi::NulError>
 
 // Get vector of Strings from double pointer to c_char.
 // Also free memory reserved by the pointer.
-fn get_string_vec(ptr: *mut *mut c_char, free_memory: bool) -> Vec<String> {
+// Invalid UTF-8 sequences in the strings are replaced with U+FFFD.
+pub fn get_string_vec(ptr: *mut *mut c_char, free_memory: bool) -> Vec<String> {
+    get_string_vec_limit(ptr, free_memory, usize::MAX)
+}
+
+// Like get_string_vec, but stops collecting after max strings.
+// The whole array is still freed if free_memory is set.
+pub fn get_string_vec_limit(ptr: *mut *mut c_char, free_memory: bool, max: usize) -> Vec<String> {
     let mut vect = Vec::new();
     if ptr.is_null() {
         vect
     } else {
         unsafe {
             let mut i = 0;
-            while !(*ptr.offset(i)).is_null() {
-                vect.push(String::from(
-                    ffi::CStr::from_ptr(*ptr.offset(i)).to_str().unwrap(),
-                ));
+            while vect.len() < max && !(*ptr.offset(i)).is_null() {
+                vect.push(
+                    ffi::CStr::from_ptr(*ptr.offset(i)).to_string_lossy().into_owned(),
+                );
                 i += 1;
             }
             if free_memory {
@@ -367,54 +435,190 @@ pub fn list_supported_grammar_checking_languages(path: &str) -> Result<Vec<Strin
 }
 
 pub fn analyze_word(handle: *mut VoikkoHandle, word: &str) -> Result<Vec<voikko::Analysis>, ffi::NulError> {
-    let mut vect = Vec::new();
+    analyze_word_filtered(handle, word, usize::MAX, None)
+}
+
+// Like analyze_word, but converts at most `limit` analyses, and only the given keys of each
+// analysis if `keys` is not None.
+pub fn analyze_word_filtered(
+    handle: *mut VoikkoHandle,
+    word: &str,
+    limit: usize,
+    keys: Option<&[&str]>,
+) -> Result<Vec<voikko::Analysis>, ffi::NulError> {
     let word_cstring = ffi::CString::new(word)?;
+    Ok(analyze_word_cstr(handle, &word_cstring, limit, keys))
+}
+
+// Like analyze_word_filtered, but takes an already NUL-terminated word.
+pub fn analyze_word_cstr(
+    handle: *mut VoikkoHandle,
+    word: &ffi::CStr,
+    limit: usize,
+    keys: Option<&[&str]>,
+) -> Vec<voikko::Analysis> {
+    let mut analyses = AnalysisList::from_cstr(handle, word);
+    let mut vect = Vec::new();
+    while vect.len() < limit {
+        match analyses.next_with_keys(keys) {
+            Some(analysis) => vect.push(analysis),
+            None => break,
+        }
+    }
+    vect
+}
+
+// Analyses of a word, converted one at a time. The libvoikko list is freed when this is dropped.
+pub struct AnalysisList {
+    // NULL-pointer terminated list of analyses, or NULL if there are none
+    ptr: *mut *mut voikko_mor_analysis,
+    index: isize,
+}
+
+impl AnalysisList {
+    pub fn new(handle: *mut VoikkoHandle, word: &str) -> Result<AnalysisList, ffi::NulError> {
+        let word_cstring = ffi::CString::new(word)?;
+        Ok(AnalysisList::from_cstr(handle, &word_cstring))
+    }
+
+    pub fn from_cstr(handle: *mut VoikkoHandle, word: &ffi::CStr) -> AnalysisList {
+        let ptr = unsafe { voikkoAnalyzeWordCstr(handle, word.as_ptr()) };
+        AnalysisList { ptr, index: 0 }
+    }
+
+    // Converts the next analysis, with only the given keys if `keys` is not None.
+    pub fn next_with_keys(&mut self, keys: Option<&[&str]>) -> Option<voikko::Analysis> {
+        if self.ptr.is_null() {
+            return None;
+        }
+        unsafe {
+            let analysis_ptr = *self.ptr.offset(self.index);
+            if analysis_ptr.is_null() {
+                return None;
+            }
+            self.index += 1;
+            let mut analysis = voikko::Analysis::new();
+            // get all key-value pairs for this analysis
+            let keys_ptr = voikko_mor_analysis_keys(analysis_ptr);
+            let analysis_keys = get_string_vec(keys_ptr as *mut *mut c_char, false);
+            for key in analysis_keys {
+                if keys.is_some_and(|keys| !keys.contains(&key.as_str())) {
+                    continue;
+                }
+                // keys come from C strings, so they cannot contain NUL bytes
+                let Ok(key_cstring) = ffi::CString::new(key.as_str()) else {
+                    continue;
+                };
+                let value_ptr = voikko_mor_analysis_value_cstr(analysis_ptr, key_cstring.as_ptr());
+                if value_ptr.is_null() {
+                    continue;
+                }
+                let value = ffi::CStr::from_ptr(value_ptr).to_str().unwrap_or_default();
+                // insert key-value pair
+                analysis.insert(key, String::from(value));
+                voikko_free_mor_analysis_value_cstr(value_ptr);
+            }
+            Some(analysis)
+        }
+    }
+}
+
+impl Iterator for AnalysisList {
+    type Item = voikko::Analysis;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_keys(None)
+    }
+}
+
+impl Drop for AnalysisList {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            unsafe {
+                voikko_free_mor_analysis(self.ptr);
+            }
+        }
+    }
+}
+
+// Check whether there is at least one grammar error in the text, fetching only the first one.
+pub fn has_grammar_error(handle: *mut VoikkoHandle, text: &str) -> Result<bool, ffi::NulError> {
+    let text_cstring = ffi::CString::new(text)?;
     unsafe {
-        // NULL-pointer terminated list of analyses
-        let analysis_list_ptr =
-            voikkoAnalyzeWordCstr(handle, word_cstring.as_ptr());
-        if analysis_list_ptr.is_null() {
-            Ok(vect)
+        let grammar_error_ptr =
+            voikkoNextGrammarErrorCstr(handle, text_cstring.as_ptr(), text.len(), 0, 0);
+        if grammar_error_ptr.is_null() {
+            Ok(false)
         } else {
-            // loop through list until NULL pointer
-            let mut i = 0;
-            while !(*analysis_list_ptr.offset(i)).is_null() {
-                let mut analysis = voikko::Analysis::new();
-                // get all key-value pairs for this analysis
-                let keys_ptr = voikko_mor_analysis_keys(*analysis_list_ptr.offset(i));
-                let keys = get_string_vec(keys_ptr as *mut *mut c_char, false);
-                for key in keys {
-                    let key_cstring = ffi::CString::new(key.as_str())?;
-                    let value_ptr = voikko_mor_analysis_value_cstr(
-                        *analysis_list_ptr.offset(i),
-                        key_cstring.as_ptr(),
-                    );
-                    let value = ffi::CStr::from_ptr(value_ptr).to_str().unwrap_or_default();
-                    // insert key-value pair
-                    analysis.insert(key, String::from(value));
-                    voikko_free_mor_analysis_value_cstr(value_ptr);
-                }
-                // add this analysis to the return vector
-                vect.push(analysis);
-                i += 1;
+            voikkoFreeGrammarError(grammar_error_ptr);
+            Ok(true)
+        }
+    }
+}
+
+// Get the codes of all grammar errors in the text, without fetching their descriptions. The
+// errors are found as in get_grammar_errors_limit, but the codes are in libvoikko's order.
+pub fn get_grammar_error_codes(handle: *mut VoikkoHandle, text: &str) -> Result<Vec<c_int>, ffi::NulError> {
+    let text_cstring = ffi::CString::new(text)?;
+    let mut codes = Vec::new();
+    unsafe {
+        let mut skip: c_int = 0;
+        loop {
+            let grammar_error_ptr =
+                voikkoNextGrammarErrorCstr(handle, text_cstring.as_ptr(), text.len(), 0, skip);
+            if grammar_error_ptr.is_null() {
+                break;
             }
-            voikko_free_mor_analysis(analysis_list_ptr);
-            Ok(vect)
+            codes.push(voikkoGetGrammarErrorCode(grammar_error_ptr));
+            voikkoFreeGrammarError(grammar_error_ptr);
+            skip += 1;
         }
     }
+    Ok(codes)
+}
+
+// Get the short description of a grammar error in given language.
+unsafe fn grammar_error_description(
+    grammar_error_ptr: *mut VoikkoGrammarError,
+    desc_lang: &ffi::CStr,
+) -> Result<String, voikko::GrammarCheckError> {
+    let desc_ptr = voikkoGetGrammarErrorShortDescription(grammar_error_ptr, desc_lang.as_ptr());
+    if desc_ptr.is_null() {
+        return Err(voikko::GrammarCheckError::new(
+            "Error getting grammar error description: null pointer from libvoikko",
+        ));
+    }
+    let description = ffi::CStr::from_ptr(desc_ptr).to_str().map(str::to_string);
+    voikkoFreeErrorMessageCstr(desc_ptr);
+    Ok(description?)
 }
 
+// 'descriptions' caches error descriptions by (error code, description language). it is kept
+// by the caller across calls, so each description is fetched from libvoikko only once.
 pub fn get_grammar_errors(
     handle: *mut VoikkoHandle,
     text: &str,
     desc_lang: &str,
-) -> Result<Vec<voikko::GrammarError>, ffi::NulError> {
+    descriptions: &mut HashMap<(c_int, String), String>,
+) -> Result<Vec<voikko::GrammarError>, voikko::GrammarCheckError> {
+    get_grammar_errors_limit(handle, text, desc_lang, usize::MAX, descriptions)
+}
+
+// Like get_grammar_errors, but stops after `limit` errors.
+pub fn get_grammar_errors_limit(
+    handle: *mut VoikkoHandle,
+    text: &str,
+    desc_lang: &str,
+    limit: usize,
+    descriptions: &mut HashMap<(c_int, String), String>,
+) -> Result<Vec<voikko::GrammarError>, voikko::GrammarCheckError> {
     let mut vect: Vec<voikko::GrammarError> = Vec::new();
+    let input_text_cstr = ffi::CString::new(text)?;
+    let input_text_ptr: *const c_char = input_text_cstr.as_ptr().cast::<c_char>();
+    let desc_cstring = ffi::CString::new(desc_lang)?;
     unsafe {
-        let mut offset = 0;
-        loop {
-            let input_text_cstr = ffi::CString::new(text).unwrap();
-            let input_text_ptr: *const c_char = input_text_cstr.as_ptr().cast::<c_char>();
+        let mut skip: c_int = 0;
+        while vect.len() < limit {
             // get pointer to a grammar error C struct. it will be a null pointer if no (more) grammar errors found.
             // this is not documented in libvoikko.h but I checked the C++ function implementation.
             //
@@ -424,8 +628,11 @@ pub fn get_grammar_errors(
             // * length of the buffer in bytes
             // * offset in characters: which position to start searching from
             // * how many errors to skip from beginning
+            //
+            // always searching from the start and skipping the errors already seen means
+            // overlapping errors are not lost, unlike when advancing the offset past each error.
             let grammar_error_ptr =
-                voikkoNextGrammarErrorCstr(handle, input_text_ptr, text.len(), offset, 0);
+                voikkoNextGrammarErrorCstr(handle, input_text_ptr, text.len(), 0, skip);
             if grammar_error_ptr.is_null() {
                 voikkoFreeGrammarError(grammar_error_ptr);
                 break;
@@ -437,29 +644,37 @@ pub fn get_grammar_errors(
             let error_length = voikkoGetGrammarErrorLength(grammar_error_ptr);
             let suggestions_ptr = voikkoGetGrammarErrorSuggestions(grammar_error_ptr);
             let suggestions = get_string_vec(suggestions_ptr as *mut *mut c_char, false);
-            let desc_cstring = ffi::CString::new(desc_lang)?;
-            let desc_ptr = voikkoGetGrammarErrorShortDescription(
-                grammar_error_ptr,
-                desc_cstring.as_ptr(),
-            );
-            let desc_str = ffi::CStr::from_ptr(desc_ptr).to_str().unwrap();
+            let key = (error_code, desc_lang.to_string());
+            let description = match descriptions.get(&key) {
+                Some(description) => description.clone(),
+                None => match grammar_error_description(grammar_error_ptr, &desc_cstring) {
+                    Ok(description) => {
+                        descriptions.insert(key, description.clone());
+                        description
+                    }
+                    Err(error) => {
+                        voikkoFreeGrammarError(grammar_error_ptr);
+                        return Err(error);
+                    }
+                },
+            };
             // push a new Rust-side GrammarError struct into the vector
             vect.push(voikko::GrammarError {
                 code: error_code,
                 start_pos,
                 length: error_length,
                 suggestions,
-                description: desc_str.to_string(),
+                description,
             });
 
             // free some memory
-            voikkoFreeErrorMessageCstr(desc_ptr);
             voikkoFreeGrammarError(grammar_error_ptr);
 
-            // increment offset for next loop
-            offset += start_pos + error_length;
+            skip += 1;
         }
     }
+    // stable sort, so errors at the same position keep libvoikko's order
+    vect.sort_by_key(|error| error.start_pos);
     Ok(vect)
 }
 
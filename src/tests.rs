@@ -17,6 +17,7 @@
 */
 
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
     #[allow(clippy::wildcard_imports)]
     use crate::voikko::*;
@@ -26,6 +27,36 @@ mod tests {
         let _v = Voikko::new("fi-x-morphoid", None).unwrap();
     }
 
+    #[test]
+    fn test_init_error() {
+        let error = Voikko::new("xx-bogus", None).err().unwrap();
+        assert!(!error.to_string().is_empty());
+        assert!(Voikko::new("fi-x-morphoid", Some("/nonexistent")).is_ok());
+    }
+
+    #[test]
+    fn test_close() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(v.spell("kuningas"), SpellReturn::SpellOk);
+        v.close();
+    }
+
+    #[test]
+    fn test_raw_handle() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let handle: *mut VoikkoHandle = unsafe { v.raw_handle() };
+        assert!(!handle.is_null());
+    }
+
+    #[test]
+    fn test_into_raw() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let handle = v.into_raw();
+        assert!(!handle.is_null());
+        let v = unsafe { Voikko::from_raw(handle) };
+        assert_eq!(v.spell("kuningas"), SpellReturn::SpellOk);
+    }
+
     #[test]
     fn test_version() {
         let version = version();
@@ -41,6 +72,14 @@ mod tests {
         assert_eq!(test1, SpellReturn::SpellFailed);
     }
 
+    #[test]
+    fn test_spell_case_insensitive() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(v.spell("kUNINGAS"), SpellReturn::SpellFailed);
+        assert_eq!(v.spell_case_insensitive("kUNINGAS"), SpellReturn::SpellOk);
+        assert_eq!(v.spell_case_insensitive("adfasdf"), SpellReturn::SpellFailed);
+    }
+
     #[test]
     fn test_suggest() {
         let v = Voikko::new("fi-x-morphoid", None).unwrap();
@@ -48,6 +87,53 @@ mod tests {
         assert_eq!(sug, vec!["kissa", "kusse", "Kessi"]);
     }
 
+    #[test]
+    fn test_check() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let check = v.check("kisse");
+        assert_eq!(check.result, SpellReturn::SpellFailed);
+        assert_eq!(check.suggestions, vec!["kissa", "kusse", "Kessi"]);
+        let check = v.check("kissa");
+        assert_eq!(check.result, SpellReturn::SpellOk);
+        assert!(check.suggestions.is_empty());
+    }
+
+    #[test]
+    fn test_string_vec_invalid_utf8() {
+        let strings = [
+            std::ffi::CString::new("kissa").unwrap(),
+            std::ffi::CString::new(vec![b'k', 0xff, b'a']).unwrap(),
+        ];
+        let mut ptrs: Vec<*mut std::os::raw::c_char> =
+            strings.iter().map(|s| s.as_ptr().cast_mut()).collect();
+        ptrs.push(std::ptr::null_mut());
+        let vect = crate::libvoikko::get_string_vec(ptrs.as_mut_ptr(), false);
+        assert_eq!(vect, vec!["kissa", "k\u{FFFD}a"]);
+    }
+
+    #[test]
+    fn test_string_vec_limit() {
+        let strings = [
+            std::ffi::CString::new("kissa").unwrap(),
+            std::ffi::CString::new("kisssa").unwrap(),
+            std::ffi::CString::new("kisa").unwrap(),
+        ];
+        let mut ptrs: Vec<*mut std::os::raw::c_char> =
+            strings.iter().map(|s| s.as_ptr().cast_mut()).collect();
+        ptrs.push(std::ptr::null_mut());
+        let vect = crate::libvoikko::get_string_vec_limit(ptrs.as_mut_ptr(), false, 2);
+        assert_eq!(vect, vec!["kissa", "kisssa"]);
+        let vect = crate::libvoikko::get_string_vec_limit(ptrs.as_mut_ptr(), false, 0);
+        assert!(vect.is_empty());
+        let vect = crate::libvoikko::get_string_vec_limit(ptrs.as_mut_ptr(), false, 10);
+        assert_eq!(vect.len(), 3);
+
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let all = v.suggest("kisse");
+        assert!(all.len() > 1);
+        assert_eq!(v.suggest_n("kisse", 1), all[..1]);
+    }
+
     #[test]
     fn test_hyphenate() {
         let v = Voikko::new("fi-x-morphoid", None).unwrap();
@@ -66,6 +152,41 @@ mod tests {
         assert_eq!(hyph3, Ok("kuor-ma-au-to".to_string()));
     }
 
+    #[test]
+    fn test_hyphenate_with() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let replace = HyphenateStyle::new("-");
+        let insert = HyphenateStyle {
+            hyphen: "-".to_string(),
+            replace: false,
+        };
+        assert_eq!(v.hyphenate_with("rei'ittää", &replace).unwrap(), "rei-it-tää");
+        assert_eq!(v.hyphenate_with("rei'ittää", &insert).unwrap(), "rei-'it-tää");
+        let plain = v.hyphenate_with("kuorma-auto", &HyphenateStyle::default()).unwrap();
+        assert_eq!(plain, "kuor-ma-au-to");
+    }
+
+    #[test]
+    fn test_hyphenate_many() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let words = ["suihkumoottorimekaanikko", "rei'ittää", "kuorma-auto"];
+        let hyphenated = v.hyphenate_many(words.iter().copied(), "-");
+        assert_eq!(hyphenated.len(), words.len());
+        for (word, result) in words.iter().zip(hyphenated) {
+            assert_eq!(Ok(result.unwrap()), v.hyphenate(word, "-"));
+        }
+    }
+
+    #[test]
+    fn test_syllables() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(
+            v.syllables("suihkumoottorimekaanikko").unwrap(),
+            vec!["suih", "ku", "moot", "to", "ri", "me", "kaa", "nik", "ko"]
+        );
+        assert_eq!(v.syllables("rei'ittää").unwrap(), vec!["rei", "it", "tää"]);
+    }
+
     #[test]
     fn test_hyphenate_new() {
         let v = Voikko::new("fi-x-morphoid", None).unwrap();
@@ -99,6 +220,74 @@ mod tests {
         assert_eq!(tokens[3], Token::new(".", TokenType::Punctuation));
     }
 
+    #[test]
+    fn test_tokens_trailing_unusual() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = "sana \u{2603}\u{1F600}\u{200B}";
+        let tokens = v.tokens(text);
+        assert_eq!(tokens[0], Token::new("sana", TokenType::Word));
+        let joined: String = tokens.iter().map(|t| t.token_text.as_str()).collect();
+        assert_eq!(joined, text);
+    }
+
+    #[test]
+    fn test_tokenize_reader() {
+        // reads at most a few bytes at a time to exercise chunk boundaries
+        struct SlowReader<R>(R);
+        impl<R: std::io::Read> std::io::Read for SlowReader<R> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let len = buf.len().min(3);
+                self.0.read(&mut buf[..len])
+            }
+        }
+
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = "juhannuksen vietto.\nSäätiedotus viikonlopuksi.\n";
+        let tokens = v
+            .tokenize_reader(std::io::Cursor::new(text))
+            .collect::<Result<Vec<Token>, VoikkoError>>()
+            .unwrap();
+        assert_eq!(tokens, v.tokens(text));
+        let slow_tokens = v
+            .tokenize_reader(SlowReader(std::io::Cursor::new(text)))
+            .collect::<Result<Vec<Token>, VoikkoError>>()
+            .unwrap();
+        assert_eq!(slow_tokens, v.tokens(text));
+
+        // long input without whitespace is not held back until the end
+        let text = "sana,".repeat(100_000);
+        let joined: String = v
+            .tokenize_reader(std::io::Cursor::new(&text))
+            .map(|token| token.unwrap().token_text)
+            .collect();
+        assert_eq!(joined, text);
+    }
+
+    #[test]
+    fn test_token_type_ord() {
+        let mut types = vec![
+            TokenType::Unknown,
+            TokenType::Whitespace,
+            TokenType::Word,
+            TokenType::Punctuation,
+            TokenType::Word,
+        ];
+        types.sort();
+        assert_eq!(
+            types,
+            vec![
+                TokenType::Word,
+                TokenType::Word,
+                TokenType::Punctuation,
+                TokenType::Whitespace,
+                TokenType::Unknown
+            ]
+        );
+        assert!(SentenceType::NoStart > SentenceType::None);
+        assert!(SentenceType::Possible > SentenceType::NoStart);
+        assert!(SentenceType::Probable > SentenceType::Possible);
+    }
+
     #[test]
 
     fn test_sentences() {
@@ -141,6 +330,14 @@ mod tests {
         assert!(langs.into_iter().any(|x| x.starts_with("fi")));
     }
 
+    #[test]
+    fn test_try_languages() {
+        let langs = try_list_supported_spelling_languages("").unwrap();
+        assert!(langs.into_iter().any(|x| x.starts_with("fi")));
+        let error = try_list_supported_hyphenation_languages("/usr/\0share");
+        assert!(matches!(error, Err(VoikkoError::Nul(_))));
+    }
+
     #[test]
     fn test_analyze() {
         let v = Voikko::new("fi-x-morphoid", None).unwrap();
@@ -164,6 +361,90 @@ mod tests {
         assert_eq!(analyses[0], comparison);
     }
 
+    #[test]
+    fn test_baseforms() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let analyses = v.analyze("kuusi");
+        let baseforms = v.baseforms("kuusi");
+        assert!(analyses.len() > baseforms.len());
+        assert!(baseforms.contains(&"kuusi".to_string()));
+        for (i, baseform) in baseforms.iter().enumerate() {
+            assert!(!baseforms[i + 1..].contains(baseform));
+        }
+        assert_eq!(v.baseforms("kaljakori"), vec!["kaljakori"]);
+    }
+
+    #[test]
+    fn test_parse_fstoutput() {
+        let symbol = |tag: &str, value: Option<&str>| FstSymbol {
+            tag: tag.to_string(),
+            value: value.map(String::from),
+        };
+        let symbols = parse_fstoutput(
+            "[Ln][Xs]504403[X][Xp]kalja[X]kalj[Sn][Ny]a[Bh][Bc][Ln][Xs]506023[X][Xp]kori[X]kor[Sn][Ny]i",
+        );
+        assert_eq!(
+            symbols,
+            vec![
+                symbol("Ln", None),
+                symbol("Xs", Some("504403")),
+                symbol("X", None),
+                symbol("Xp", Some("kalja")),
+                symbol("X", Some("kalj")),
+                symbol("Sn", None),
+                symbol("Ny", Some("a")),
+                symbol("Bh", None),
+                symbol("Bc", None),
+                symbol("Ln", None),
+                symbol("Xs", Some("506023")),
+                symbol("X", None),
+                symbol("Xp", Some("kori")),
+                symbol("X", Some("kor")),
+                symbol("Sn", None),
+                symbol("Ny", Some("i")),
+            ]
+        );
+        assert_eq!(
+            parse_fstoutput("abc[Ln"),
+            vec![symbol("", Some("abc")), symbol("", Some("[Ln"))]
+        );
+        assert!(parse_fstoutput("").is_empty());
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(Number::from("singular"), Number::Singular);
+        assert_eq!(Number::from("plural"), Number::Plural);
+        assert_eq!(Number::from("dual"), Number::Unknown("dual".to_string()));
+
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let morphology = v.morphology("kaljakori");
+        assert_eq!(morphology[0].number(), Some(Number::Singular));
+    }
+
+    #[test]
+    fn test_weight() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let morphology = v.morphology("kaljakori");
+        assert_eq!(morphology[0].weight(), None);
+
+        let mut analysis = Analysis::new();
+        analysis.insert("WEIGHT".to_string(), "1.5".to_string());
+        assert_eq!(MorphologyInfo::new(analysis).weight(), Some(1.5));
+    }
+
+    #[test]
+    fn test_analyze_text() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let analyses = v.analyze_text("Kaljakori kaatui.");
+        assert_eq!(analyses.len(), 2);
+        assert_eq!(analyses[0].0, "Kaljakori");
+        assert_eq!(analyses[1].0, "kaatui");
+        assert_eq!(analyses[0].1, v.analyze("Kaljakori"));
+        assert_eq!(analyses[1].1, v.analyze("kaatui"));
+        assert!(!analyses[1].1.is_empty());
+    }
+
     #[test]
     fn test_gc() {
         let v = Voikko::new("fi-x-morphoid", None).unwrap();
@@ -192,4 +473,1005 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_has_grammar_errors() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert!(v.has_grammar_errors("Johanneksen leipäpuu pitää pitää leivottu juureen"));
+        assert!(!v.has_grammar_errors("Kissa nukkuu."));
+    }
+
+    #[test]
+    fn test_sentences_mb() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let sentences = v.sentences("Yöllä sää on hyvä. Pääskyt lähtevät etelään.");
+        assert_eq!(
+            sentences,
+            vec![
+                Sentence::new("Yöllä sää on hyvä. ", SentenceType::Probable),
+                Sentence::new("Pääskyt lähtevät etelään.", SentenceType::None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sentence_texts() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = "Järvenpää kuuluu Uudenmaan maakuntaan. Sen naapurikunnat ovat Mäntsälä koillisessa, \
+                    Sipoo idässä ja Tuusula etelässä, lännessä sekä pohjoisessa.";
+        assert_eq!(
+            v.sentence_texts(text),
+            vec![
+                "Järvenpää kuuluu Uudenmaan maakuntaan. ",
+                "Sen naapurikunnat ovat Mäntsälä koillisessa, Sipoo idässä ja Tuusula etelässä, lännessä sekä pohjoisessa."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_words() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(v.words("juhannuksen vietto."), vec!["juhannuksen", "vietto"]);
+        assert!(v.words(" . ").is_empty());
+    }
+
+    #[test]
+    fn test_spell_normalized() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(v.spell("hELSINKI"), SpellReturn::SpellFailed);
+        assert_eq!(v.spell_normalized("hELSINKI"), SpellReturn::SpellOk);
+        assert_eq!(v.spell_normalized("kuNINGAS"), SpellReturn::SpellOk);
+        v.set_opt_accept_first_uppercase(false);
+        assert_eq!(v.spell_normalized("Kuningas"), SpellReturn::SpellOk);
+        assert_eq!(v.spell_normalized("adfasdf"), SpellReturn::SpellFailed);
+    }
+
+    #[test]
+    fn test_apply_options() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(v.options(), VoikkoOptions::default());
+        assert_eq!(v.spell("kissa2"), SpellReturn::SpellFailed);
+        let opts = VoikkoOptions {
+            ignore_numbers: true,
+            min_hyphenated_word_length: 4,
+            ..VoikkoOptions::default()
+        };
+        assert!(v.apply_options(&opts));
+        assert_eq!(v.options(), opts);
+        assert_eq!(v.spell("kissa2"), SpellReturn::SpellOk);
+        assert!(v.apply_options(&VoikkoOptions::default()));
+        assert_eq!(v.spell("kissa2"), SpellReturn::SpellFailed);
+    }
+
+    #[test]
+    fn test_sentence_count() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = "Järvenpää kuuluu Uudenmaan maakuntaan. Sen naapurikunnat ovat Mäntsälä koillisessa, \
+                    Sipoo idässä ja Tuusula etelässä, lännessä sekä pohjoisessa.";
+        assert_eq!(v.sentence_count(text), 2);
+        assert_eq!(v.sentence_count(""), 0);
+    }
+
+    #[test]
+    fn test_analyze_first() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let first = v.analyze_first("kaljakori").unwrap();
+        assert_eq!(first, v.analyze("kaljakori")[0]);
+        assert_eq!(first["BASEFORM"], "kaljakori");
+        assert_eq!(v.analyze_first("kuusi"), v.analyze("kuusi").into_iter().next());
+        assert_eq!(v.analyze_first("adfasdf"), None);
+    }
+
+    #[test]
+    fn test_parse_language_tag() {
+        let parts = |l: &str, s: &str, v: &str| (l.to_string(), s.to_string(), v.to_string());
+        assert_eq!(parse_language_tag("fi"), parts("fi", "", ""));
+        assert_eq!(parse_language_tag("fi-x-morphoid"), parts("fi", "", "morphoid"));
+        assert_eq!(parse_language_tag("fi-Latn-x-morpho-test"), parts("fi", "Latn", "morpho-test"));
+        assert_eq!(parse_language_tag("fi-FI-Latn"), parts("fi", "Latn", ""));
+        let dict = Dictionary::new("fi", "Latn", "morphoid", "");
+        assert_eq!(dict.language_tag(), "fi-Latn-x-morphoid");
+        assert_eq!(parse_language_tag(&dict.language_tag()), parts("fi", "Latn", "morphoid"));
+        assert_eq!(Dictionary::new("fi", "", "", "").language_tag(), "fi");
+    }
+
+    #[test]
+    fn test_gc_suggestions() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let errors = v.grammar_errors(
+            "Johanneksen leipäpuu pitää pitää leivottu juureen",
+            "en",
+        );
+        let suggestions: Vec<&str> = errors[0].suggestions().collect();
+        assert_eq!(suggestions, vec!["pitää"]);
+        assert_eq!(errors[1].suggestions().next(), None);
+    }
+
+    #[test]
+    fn test_dictionaries_available() {
+        assert!(dictionaries_available(None));
+        let error = Voikko::new("xx-x-bogus", None).err().unwrap();
+        assert!(error.to_string().starts_with("no dictionary found for language tag \"xx-x-bogus\""));
+        let error = Voikko::new("-x-", None).err().unwrap();
+        assert!(error.to_string().starts_with("malformed language tag"));
+    }
+
+    #[test]
+    fn test_gc_by_paragraph() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = "Talo on iso.\n\n  \nKoira juoksee juoksee.\nKissa on iso\n";
+        let errors = v.grammar_errors_by_paragraph(text, "en");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].code, 8);
+        let start = errors[0].start_pos;
+        let chars: String = text.chars().skip(start).take(errors[0].length).collect();
+        assert_eq!(chars, "juoksee juoksee");
+        assert_eq!(errors[1].code, 9);
+        let chars: String = text.chars().skip(errors[1].start_pos).take(errors[1].length).collect();
+        assert_eq!(chars, "iso");
+        assert!(v.grammar_errors_by_paragraph("", "en").is_empty());
+    }
+
+    #[test]
+    fn test_token_is_significant() {
+        let tokens = [
+            Token::new("kissa", TokenType::Word),
+            Token::new(" ", TokenType::Whitespace),
+            Token::new(",", TokenType::Punctuation),
+            Token::new("\u{1f600}", TokenType::Unknown),
+            Token::new("", TokenType::None),
+        ];
+        let significant: Vec<bool> = tokens.iter().map(Token::is_significant).collect();
+        assert_eq!(significant, vec![true, false, true, true, false]);
+    }
+
+    #[test]
+    fn test_drop_null_handle() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let handle = v.into_raw();
+        drop(unsafe { Voikko::from_raw(handle) });
+        drop(unsafe { Voikko::from_raw(std::ptr::null_mut()) });
+    }
+
+    #[test]
+    fn test_spell_trimmed() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(v.spell("sana,"), SpellReturn::SpellFailed);
+        assert_eq!(v.spell_trimmed("sana,"), SpellReturn::SpellOk);
+        assert_eq!(v.spell_trimmed("\"sana\""), SpellReturn::SpellOk);
+        assert_eq!(v.spell_trimmed("«kissa»…"), SpellReturn::SpellOk);
+        assert_eq!(v.spell_trimmed("‹kissa›"), SpellReturn::SpellOk);
+        assert_eq!(v.spell_trimmed("¡kissa!"), SpellReturn::SpellOk);
+        assert_eq!(v.spell_trimmed("¿kissa?"), SpellReturn::SpellOk);
+        assert_eq!(v.spell_trimmed("「kissa」"), SpellReturn::SpellOk);
+        assert_eq!(v.spell_trimmed("(kuorma-auto)."), SpellReturn::SpellOk);
+        assert_eq!(v.spell_trimmed("esim."), SpellReturn::SpellOk);
+        assert_eq!(v.spell_trimmed("\"adfasdf\""), SpellReturn::SpellFailed);
+        assert_eq!(v.spell_trimmed("?!"), SpellReturn::SpellFailed);
+    }
+
+    #[test]
+    fn test_text_stats() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let stats = v.text_stats("Talo on iso. Koira juoksee, kissa ei!");
+        assert_eq!(stats.word_count, 7);
+        assert_eq!(stats.sentence_count, 2);
+        assert!((stats.average_sentence_length - 3.5).abs() < f64::EPSILON);
+        assert_eq!(stats.punctuation_count, 3);
+        assert_eq!(v.text_stats("").sentence_count, 0);
+    }
+
+    #[test]
+    fn test_parse_wordids() {
+        let word_id = |surface: &str, id: &str| WordId {
+            surface: surface.to_string(),
+            id: id.to_string(),
+        };
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let morphology = v.morphology("kaljakori");
+        assert_eq!(
+            morphology[0].word_ids(),
+            vec![word_id("kalja", "w504403"), word_id("kori", "w506023")]
+        );
+        assert_eq!(
+            parse_wordids("+kuorma(w1)-+auto"),
+            vec![word_id("kuorma", "w1"), word_id("auto", "")]
+        );
+        assert!(parse_wordids("").is_empty());
+    }
+
+    #[test]
+    fn test_gc_default_desc_lang() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        v.set_default_grammar_desc_lang("en");
+        let text = "Johanneksen leipäpuu pitää pitää leivottu juureen";
+        let errors = v.grammar_errors_default(text);
+        assert_eq!(errors, v.grammar_errors(text, "en"));
+        assert_eq!(errors[0].description, "Remove duplicate word.");
+        assert_eq!(errors[1].description, "Terminating punctuation is missing.");
+    }
+
+    #[test]
+    fn test_review_text() {
+        let text = "Isö kisse juoksee.";
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let items = v.review_text(text);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].word, "Isö");
+        assert_eq!(
+            items[1],
+            ReviewItem {
+                word: "kisse".to_string(),
+                start: 5,
+                length: 5,
+                suggestions: vec!["kissa".to_string(), "kusse".to_string(), "Kessi".to_string()],
+            }
+        );
+        assert_eq!(&text[items[1].start..items[1].start + items[1].length], "kisse");
+    }
+
+    #[test]
+    fn test_error_source() {
+        use std::error::Error;
+        let error = Voikko::new("fi\0", None).err().unwrap();
+        assert!(error.source().is_some());
+        assert!(Voikko::new("xx-bogus", None).err().unwrap().source().is_none());
+        let nul_error = std::ffi::CString::new("kis\0sa").unwrap_err();
+        assert!(HyphenateError::from(nul_error).source().is_some());
+        assert!(HyphenateError::new("failed").source().is_none());
+    }
+
+    #[test]
+    fn test_fully_supported_languages() {
+        let langs = list_fully_supported_languages("");
+        assert!(langs.into_iter().any(|x| x.starts_with("fi")));
+    }
+
+    #[test]
+    fn test_spell_lines() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let input = std::io::Cursor::new("kissa\n  kisse \n\nkuningas");
+        let mut output = Vec::new();
+        v.spell_lines(input, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "kissa\tOK\nkisse\tWRONG\n\nkuningas\tOK\n"
+        );
+    }
+
+    #[test]
+    fn test_analysis_semantic_eq() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let a = v.analyze("kaljakori").remove(0);
+        let mut b = a.clone();
+        b.insert("FSTOUTPUT".to_string(), "[Ln][Xs]1[X][Xp]kaljakori".to_string());
+        assert_ne!(a, b);
+        assert!(analysis_semantic_eq(&a, &b));
+        assert!(!analysis_eq_by_keys(&a, &b, &["FSTOUTPUT"]));
+        b.insert("NUMBER".to_string(), "plural".to_string());
+        assert!(!analysis_semantic_eq(&a, &b));
+    }
+
+    #[test]
+    fn test_gc_limited() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = "Johanneksen leipäpuu pitää pitää leivottu juureen";
+        let errors = v.grammar_errors_limited(text, "en", 1);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0], v.grammar_errors(text, "en")[0]);
+        assert!(v.grammar_errors_limited(text, "en", 0).is_empty());
+        assert_eq!(v.grammar_errors_limited(text, "en", 5).len(), 2);
+    }
+
+    #[test]
+    fn test_grapheme_len() {
+        // "Järvenpää" with combining diaeresis
+        let token = Token::new("Ja\u{308}rvenpa\u{308}a\u{308}", TokenType::Word);
+        assert_eq!(token.token_text.chars().count(), 12);
+        assert_eq!(token.grapheme_len(), 9);
+        let sentence = Sentence::new("Ja\u{308}rvenpa\u{308}a\u{308}. ", SentenceType::None);
+        assert_eq!(sentence.grapheme_len(), 11);
+    }
+
+    #[test]
+    fn test_spell_any() {
+        let strict = Voikko::new("fi-x-morphoid", None).unwrap();
+        strict.set_opt_accept_first_uppercase(false);
+        let voikkos = [strict, Voikko::new("fi-x-morphoid", None).unwrap()];
+        assert_eq!(voikkos[0].spell("Kuningas"), SpellReturn::SpellFailed);
+        assert!(spell_any(&voikkos, "Kuningas"));
+        assert!(spell_any(&voikkos, "kuningas"));
+        assert!(!spell_any(&voikkos, "kuninggas"));
+        assert!(!spell_any(&[], "kuningas"));
+    }
+
+    #[test]
+    fn test_default_dictionary() {
+        let dict = default_dictionary("fi", "").unwrap();
+        assert_eq!(dict.language, "fi");
+        assert_eq!(default_dictionary("fi-x-standard", "").unwrap().variant, "standard");
+        assert_eq!(default_dictionary("xx", ""), None);
+    }
+
+    #[test]
+    fn test_token_tuple_conversion() {
+        let token = Token::new("kissa", TokenType::Word);
+        let pair: (String, TokenType) = token.into();
+        assert_eq!(pair, ("kissa".to_string(), TokenType::Word));
+        assert_eq!(Token::from(pair), Token::new("kissa", TokenType::Word));
+    }
+
+    #[test]
+    fn test_hyphenate_detecting_changes() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(
+            v.hyphenate_detecting_changes("rei'ittää", "-").unwrap(),
+            ("rei-it-tää".to_string(), true)
+        );
+        assert_eq!(
+            v.hyphenate_detecting_changes("kuorma-auto", "-").unwrap(),
+            ("kuor-ma-au-to".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn test_analyze_keys() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let analyses = v.analyze_keys("kaljakori", &["BASEFORM", "CLASS"]);
+        let mut comparison = Analysis::new();
+        comparison.insert("CLASS".to_string(), "nimisana".to_string());
+        comparison.insert("BASEFORM".to_string(), "kaljakori".to_string());
+        assert_eq!(analyses, vec![comparison]);
+        // the number of analyses depends on the dictionary, so only check for expected ones
+        let analyses = v.analyze_keys("kuusi", &["BASEFORM", "CLASS"]);
+        for (baseform, class) in [("kuusi", "lukusana"), ("kuusi", "nimisana"), ("kuu", "nimisana")] {
+            assert!(analyses
+                .iter()
+                .any(|analysis| analysis.baseform() == Some(baseform) && analysis.word_class() == Some(class)));
+        }
+        assert!(v.analyze_keys("kuusi", &[]).iter().all(|analysis| analysis.is_empty()));
+    }
+
+    #[test]
+    fn test_hyphenate_cow() {
+        use std::borrow::Cow;
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let word = String::from("kuu");
+        assert!(matches!(v.hyphenate_cow(&word, "-"), Ok(Cow::Borrowed("kuu"))));
+        let hyphenated = v.hyphenate_cow("kaljakori", "-").unwrap();
+        assert!(matches!(hyphenated, Cow::Owned(_)));
+        assert_eq!(hyphenated, "kal-ja-ko-ri");
+    }
+
+    #[test]
+    fn test_sentences_min_confidence() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = "Talo on iso. talo on iso. Koira juoksee.";
+        assert_eq!(
+            v.sentences_min_confidence(text, SentenceType::Possible),
+            vec!["Talo on iso. ", "talo on iso. ", "Koira juoksee."]
+        );
+        assert_eq!(
+            v.sentences_min_confidence(text, SentenceType::Probable),
+            vec!["Talo on iso. talo on iso. ", "Koira juoksee."]
+        );
+    }
+
+    #[test]
+    fn test_tokens_long_text() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = "Järvenpää kuuluu Uudenmaan maakuntaan. ".repeat(50_000);
+        let tokens = v.tokens(&text);
+        assert_eq!(tokens.len(), 50_000 * 9);
+        assert_eq!(tokens[0], Token::new("Järvenpää", TokenType::Word));
+        assert_eq!(v.sentence_count(&text), 50_000);
+        assert!(v.tokens("kissa\0kissa").is_empty());
+
+        // the time taken must grow linearly with the length of the text: twice the text taking
+        // four times as long means each token or sentence lookup scans the rest of the text
+        let time = |repeat: usize| {
+            let text = "Järvenpää kuuluu Uudenmaan maakuntaan. ".repeat(repeat);
+            (0..3)
+                .map(|_| {
+                    let start = std::time::Instant::now();
+                    assert_eq!(v.tokens(&text).len(), repeat * 9);
+                    assert_eq!(v.sentence_count(&text), repeat);
+                    start.elapsed()
+                })
+                .min()
+                .unwrap()
+        };
+        let short = time(10_000);
+        let long = time(20_000);
+        assert!(long < short * 3, "{:?} for 20000 repeats, {:?} for 10000", long, short);
+    }
+
+    #[test]
+    fn test_validate_input() {
+        assert!(validate_input("Järvenpää kuuluu Uudenmaan maakuntaan.").is_ok());
+        assert!(matches!(validate_input("kis\0sa"), Err(VoikkoError::Nul(_))));
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert!(v.grammar_errors("pitää pitää\0", "en").is_empty());
+        assert!(v.sentences("Talo on iso.\0").is_empty());
+        assert_eq!(v.spell("kis\0sa"), SpellReturn::SpellFailed);
+        assert_eq!(v.suggest_ranked("kis\0se").count(), 0);
+        assert_eq!(v.text_stats("Talo on iso.\0").word_count, 0);
+        assert_eq!(v.canonical_key("Kis\0sa"), "kis\0sa");
+        assert!(matches!(v.hyphenate_with("kis\0sa", &HyphenateStyle::new("-")), Err(VoikkoError::Hyphenate(_))));
+        assert!(v.hyphenate_new("kissa", "\0", false).is_err());
+    }
+
+    #[test]
+    fn test_tokens_with_spelling() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let tokens = v.tokens_with_spelling("Kisse juoksee.");
+        assert_eq!(
+            tokens,
+            vec![
+                (Token::new("Kisse", TokenType::Word), Some(SpellReturn::SpellFailed)),
+                (Token::new(" ", TokenType::Whitespace), None),
+                (Token::new("juoksee", TokenType::Word), Some(SpellReturn::SpellOk)),
+                (Token::new(".", TokenType::Punctuation), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_suggest_ranked() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let mut ranked = v.suggest_ranked("kisse");
+        assert_eq!(ranked.next(), Some((1, "kissa".to_string())));
+        assert_eq!(ranked.next(), Some((2, "kusse".to_string())));
+        assert_eq!(v.suggest_ranked("kissa").next(), None);
+    }
+
+    #[test]
+    fn test_analysis_accessors() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let analysis = v.analyze_first("kaljakori").unwrap();
+        assert_eq!(analysis.baseform(), Some("kaljakori"));
+        assert_eq!(analysis.word_class(), Some("nimisana"));
+        assert_eq!(analysis.get("SIJAMUOTO"), Some("nimento"));
+        assert_eq!(analysis.get("MOOD"), None);
+        // the map is still available through Deref
+        assert!(analysis.contains_key("FSTOUTPUT"));
+        assert_eq!(analysis.into_inner()["NUMBER"], "singular");
+    }
+
+    #[test]
+    fn test_analyses_lazy() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(v.analyses("kaljakori").next(), Some(v.analyze("kaljakori").remove(0)));
+        assert_eq!(v.analyses("kuusi").collect::<Vec<_>>(), v.analyze("kuusi"));
+        let kuu = v.analyses("kuusi").find(|analysis| analysis.baseform() == Some("kuu"));
+        assert!(kuu.is_some());
+        assert_eq!(v.analyses("adfasdf").next(), None);
+        assert_eq!(v.analyses("kis\0sa").next(), None);
+    }
+
+    #[test]
+    fn test_normalize_apostrophes() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = "rei\u{2019}ittää on";
+        assert_eq!(v.tokens(text)[0], Token::new("rei", TokenType::Word));
+        v.set_normalize_apostrophes(true);
+        let tokens = v.tokens(text);
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new("rei\u{2019}ittää", TokenType::Word),
+                Token::new(" ", TokenType::Whitespace),
+                Token::new("on", TokenType::Word),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hyphenate_with_orphans() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(v.hyphenate_with_orphans("iso", "-", 2, 2).unwrap(), "iso");
+        assert_eq!(v.hyphenate_with_orphans("iso", "-", 1, 1).unwrap(), "i-so");
+        assert_eq!(v.hyphenate_with_orphans("kaljakori", "-", 3, 3).unwrap(), "kal-ja-kori");
+        assert_eq!(v.hyphenate_with_orphans("kuorma-auto", "-", 2, 4).unwrap(), "kuor-ma-auto");
+        assert_eq!(v.hyphenate_with_orphans("kuorma-auto", "-", 2, 5).unwrap(), "kuor-ma-auto");
+        // "a\u{308}" is one grapheme, so only two characters follow the hyphenation point
+        assert_eq!(v.hyphenate_with_orphans("kissa\u{308}", "-", 1, 2).unwrap(), "kis-sa\u{308}");
+        assert_eq!(v.hyphenate_with_orphans("kissa\u{308}", "-", 1, 3).unwrap(), "kissa\u{308}");
+    }
+
+    #[test]
+    fn test_backend_info() {
+        let info = backend_info();
+        assert!(info.version.starts_with("4."));
+        assert!(info.has_grammar_checker);
+        assert_eq!(info.dictionary_count, list_dicts("").len());
+    }
+
+    #[test]
+    fn test_spell_session() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let mut session = v.spell_session();
+        for word in ["suihkumoottorimekaanikko", "kissa", "kisse", "Kuningas", "", "kis\0sa"] {
+            assert_eq!(session.spell(word), v.spell(word), "{word:?}");
+        }
+    }
+
+    #[test]
+    fn test_gc_category() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let errors = v.grammar_errors(
+            "Johanneksen leipäpuu pitää pitää leivottu juureen",
+            "en",
+        );
+        assert_eq!(errors[0].category(), GrammarErrorCategory::Repetition);
+        assert_eq!(errors[1].category(), GrammarErrorCategory::Punctuation);
+    }
+
+    #[test]
+    fn test_token_refs() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = String::from("Kissa ja koira.");
+        let token_refs = v.token_refs(&text);
+        assert_eq!(token_refs[0], TokenRef::new("Kissa", TokenType::Word));
+        let words: Vec<Token> = token_refs
+            .into_iter()
+            .filter(|token| token.token_type == TokenType::Word)
+            .map(Token::from)
+            .collect();
+        drop(text);
+        assert_eq!(
+            words,
+            vec![
+                Token::new("Kissa", TokenType::Word),
+                Token::new("ja", TokenType::Word),
+                Token::new("koira", TokenType::Word),
+            ]
+        );
+        assert_eq!(TokenRef::new(".", TokenType::Punctuation).to_token(), Token::new(".", TokenType::Punctuation));
+    }
+
+    #[test]
+    fn test_spell_with_dot_handling() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(v.spell_with_dot_handling("esim.", false), SpellReturn::SpellOk);
+        assert_eq!(v.spell_with_dot_handling("esim.", true), SpellReturn::SpellOk);
+        assert_eq!(v.spell_with_dot_handling("kissa.", false), SpellReturn::SpellFailed);
+        assert_eq!(v.spell_with_dot_handling("kissa.", true), SpellReturn::SpellOk);
+        assert!(!v.options().ignore_dot);
+        v.set_opt_ignore_dot(true);
+        assert_eq!(v.spell_with_dot_handling("kissa.", false), SpellReturn::SpellFailed);
+        assert!(v.options().ignore_dot);
+    }
+
+    #[test]
+    fn test_supports_features() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert!(v.supports_grammar_checking());
+        assert!(v.supports_hyphenation());
+        let v = unsafe { Voikko::from_raw(v.into_raw()) };
+        assert!(!v.supports_grammar_checking());
+    }
+
+    #[test]
+    fn test_hyphenate_soft() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(v.hyphenate_soft("kaljakori").unwrap(), "kal\u{ad}ja\u{ad}ko\u{ad}ri");
+        assert_eq!(v.hyphenate_soft("kuorma-auto").unwrap(), "kuor\u{ad}ma-au\u{ad}to");
+        assert_eq!(v.hyphenate_soft("rei'ittää").unwrap(), "rei\u{ad}it\u{ad}tää");
+    }
+
+    #[test]
+    fn test_word_classes() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let classes = v.word_classes("kuusi");
+        assert!(classes.contains(&WordClass::Noun));
+        assert!(classes.contains(&WordClass::Numeral));
+        for (i, class) in classes.iter().enumerate() {
+            assert!(!classes[i + 1..].contains(class));
+        }
+        assert_eq!(v.word_classes("kaljakori"), vec![WordClass::Noun]);
+    }
+
+    #[test]
+    fn test_detokenize() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let samples = [
+            "",
+            "kissa",
+            "Kissa ja koira.",
+            "  Kaksi  välilyöntiä\tja sarkain!\n",
+            "Numeroita 1,5 ja 42 sekä merkkejä: #@%",
+            "Se on kahvi\u{2019}n hinta? Niin...",
+        ];
+        for text in &samples {
+            assert_eq!(detokenize(&v.tokens(text)), *text);
+        }
+    }
+
+    #[test]
+    fn test_gc_sorted() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let errors = v.grammar_errors(
+            "Kissa kissa nukkuu ja koira koira juoksee sen sen talo",
+            "en",
+        );
+        assert!(errors.len() > 2);
+        assert!(errors.windows(2).all(|pair| pair[0].start_pos <= pair[1].start_pos));
+    }
+
+    #[test]
+    fn test_variants_for_language() {
+        let variants = variants_for_language("fi", "");
+        assert!(variants.contains(&"morphoid".to_string()));
+        assert!(variants.contains(&"standard".to_string()));
+        for (i, variant) in variants.iter().enumerate() {
+            assert!(!variants[i + 1..].contains(variant));
+        }
+        assert!(variants_for_language("xx", "").is_empty());
+    }
+
+    #[test]
+    fn test_hyphenation_is_lossless() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(v.hyphenation_is_lossless("rei'ittää").ok(), Some(false));
+        assert_eq!(v.hyphenation_is_lossless("suihkumoottorimekaanikko").ok(), Some(true));
+        assert!(v.hyphenation_is_lossless("rei\0ittää").is_err());
+    }
+
+    #[test]
+    fn test_tokens_normalized() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let tokens = v.tokens_normalized("Kissa  ja\t koira.\n");
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new("Kissa", TokenType::Word),
+                Token::new(" ", TokenType::Whitespace),
+                Token::new("ja", TokenType::Word),
+                Token::new(" ", TokenType::Whitespace),
+                Token::new("koira", TokenType::Word),
+                Token::new(".", TokenType::Punctuation),
+                Token::new(" ", TokenType::Whitespace),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_speller_cache_bytes() {
+        use crate::voikko::speller_cache_exponent;
+        assert_eq!(speller_cache_exponent(0), 0);
+        assert_eq!(speller_cache_exponent(27184), 0);
+        assert_eq!(speller_cache_exponent(100_000), 2);
+        assert_eq!(speller_cache_exponent(1 << 20), 5);
+
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert!(v.set_speller_cache_bytes(100_000));
+        assert_eq!(v.options().speller_cache_size, 2);
+        assert!(v.set_speller_cache_disabled());
+        assert_eq!(v.options().speller_cache_size, -1);
+    }
+
+    #[test]
+    fn test_try_hyphens() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(
+            v.try_hyphens("suihkumoottorimekaanikko").ok(),
+            v.hyphens("suihkumoottorimekaanikko").ok()
+        );
+        let error = v.try_hyphens("suihku\0moottori").unwrap_err();
+        assert!(matches!(error, VoikkoError::Hyphenate(_)));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn test_gc_descriptions() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = "Johanneksen leipäpuu pitää pitää leivottu juureen sen sen";
+        let errors = v.grammar_errors(text, "en");
+        let codes: Vec<i32> = errors.iter().map(|error| error.code).collect();
+        assert_eq!(codes, vec![8, 8, 9]);
+        for error in &errors {
+            let expected = match error.code {
+                8 => "Remove duplicate word.",
+                _ => "Terminating punctuation is missing.",
+            };
+            assert_eq!(error.description, expected);
+        }
+        // cached descriptions are reused by later calls
+        assert_eq!(v.grammar_errors(text, "en"), errors);
+        assert_eq!(v.grammar_errors_limited(text, "en", 1)[..], errors[..1]);
+    }
+
+    #[test]
+    fn test_is_single_word() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert!(v.is_single_word("sana"));
+        assert!(!v.is_single_word("kaksi sanaa"));
+        assert!(!v.is_single_word("sana!"));
+        assert!(!v.is_single_word(" sana"));
+        assert!(!v.is_single_word(""));
+    }
+
+    #[test]
+    fn test_hyphenation() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let word = "suihkumoottorimekaanikko";
+        let hyphenation = v.hyphenation(word).unwrap();
+        assert_eq!(hyphenation.as_str(), "    - -   - - - -  -  - ");
+        assert_eq!(hyphenation.points(), vec![4, 6, 10, 12, 14, 16, 19, 22]);
+        assert_eq!(hyphenation.apply(word, "-"), "suih-ku-moot-to-ri-me-kaa-nik-ko");
+        let hyphenation = v.hyphenation("rei'ittää").unwrap();
+        assert_eq!(hyphenation.points(), vec![3, 6]);
+        assert_eq!(hyphenation.apply("rei'ittää", "-"), "rei-it-tää");
+    }
+
+    #[test]
+    fn test_inflection_class() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        // read from the last [L…] tag of FSTOUTPUT, "[Ln]...kalja...[Ln]...kori..."
+        assert_eq!(v.inflection_class("kaljakori"), Some("n".to_string()));
+        assert_eq!(v.inflection_class("kuninggas"), None);
+    }
+
+    #[test]
+    fn test_gc_eq_ignoring_suggestion_order() {
+        let error = |suggestions: &[&str]| GrammarError {
+            code: 8,
+            start_pos: 21,
+            length: 11,
+            suggestions: suggestions.iter().map(|&s| String::from(s)).collect(),
+            description: "Remove duplicate word.".to_string(),
+        };
+        let a = error(&["pitää", "pitää pitää"]);
+        let b = error(&["pitää pitää", "pitää"]);
+        assert_ne!(a, b);
+        assert!(a.eq_ignoring_suggestion_order(&b));
+        assert!(!a.eq_ignoring_suggestion_order(&error(&["pitää"])));
+        let mut c = error(&["pitää", "pitää pitää"]);
+        c.start_pos = 22;
+        assert!(!a.eq_ignoring_suggestion_order(&c));
+    }
+
+    #[test]
+    fn test_hyphenate_compound_parts() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let parts = v.hyphenate_compound_parts("kaljakori", "-").unwrap();
+        assert_eq!(parts, vec!["kal-ja", "ko-ri"]);
+        let parts = v.hyphenate_compound_parts("kissa", "-").unwrap();
+        assert_eq!(parts, vec!["kis-sa"]);
+        // an inflected non-compound is hyphenated as given, not in its base form
+        let parts = v.hyphenate_compound_parts("kissan", "-").unwrap();
+        assert_eq!(parts, vec!["kis-san"]);
+        assert!(v.hyphenate_compound_parts("kal\0ja", "-").is_err());
+    }
+
+    #[test]
+    fn test_try_grammar_errors() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = "Johanneksen leipäpuu pitää pitää leivottu juureen";
+        let errors = v.try_grammar_errors(text, "en").unwrap();
+        assert_eq!(errors, v.grammar_errors(text, "en"));
+        // unknown description languages fall back to a default language in libvoikko
+        let errors = v.try_grammar_errors(text, "xx-bogus").unwrap();
+        assert!(errors.iter().all(|error| !error.description.is_empty()));
+        let error = v.try_grammar_errors(text, "e\0n").unwrap_err();
+        assert!(matches!(error, VoikkoError::GrammarCheck(_)));
+        assert!(std::error::Error::source(&error).is_some());
+        assert_eq!(
+            format!("{}", GrammarCheckError::new("description failed")),
+            "description failed"
+        );
+    }
+
+    #[test]
+    fn test_avg_syllables_per_word() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let average = v.avg_syllables_per_word("kissa, talo ja iso.").unwrap();
+        assert!((average - 7.0 / 4.0).abs() < 1e-9);
+        assert!(v.avg_syllables_per_word("...").unwrap().abs() < 1e-9);
+        assert!(v.avg_syllables_per_word("kis\0sa").is_err());
+    }
+
+    #[test]
+    fn test_best_suggestion() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(v.best_suggestion("kuninggas"), Some(("kuningas".to_string(), 1)));
+        assert_eq!(v.best_suggestion("kisse"), Some(("kissa".to_string(), 1)));
+    }
+
+    #[test]
+    fn test_dictionary_summaries() {
+        let summaries = dictionary_summaries("");
+        assert_eq!(summaries.len(), list_dicts("").len());
+        let (_, description) = summaries
+            .iter()
+            .find(|(tag, _)| tag == "fi-x-morphoid")
+            .unwrap();
+        assert!(!description.is_empty());
+    }
+
+    #[test]
+    fn test_token_byte_len() {
+        use crate::voikko::token_byte_len;
+        assert_eq!(token_byte_len("kissa ja koira", 5), 5);
+        assert_eq!(token_byte_len("äiti", 2), 3);
+        assert_eq!(token_byte_len("kissa", 0), 1);
+        assert_eq!(token_byte_len("äiti", 0), 2);
+        assert_eq!(token_byte_len("e\u{301}tä", 0), 3);
+        assert_eq!(token_byte_len("", 0), 0);
+    }
+
+    #[test]
+    fn test_multi_voikko() {
+        let strict = Voikko::new("fi-x-morphoid", None).unwrap();
+        let lenient = Voikko::new("fi-x-morphoid", None).unwrap();
+        lenient.set_opt_ignore_numbers(true);
+        let multi = MultiVoikko::new(vec![strict, lenient]);
+        assert_eq!(multi.voikkos().len(), 2);
+        assert_eq!(multi.spell("kissa"), (SpellReturn::SpellOk, 0));
+        assert_eq!(multi.spell("kissa123"), (SpellReturn::SpellOk, 1));
+        assert_eq!(multi.spell("kuninggas"), (SpellReturn::SpellFailed, 0));
+        let empty = MultiVoikko::new(vec![]);
+        assert_eq!(empty.spell("kissa"), (SpellReturn::SpellFailed, 0));
+    }
+
+    #[test]
+    fn test_canonical_key() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(v.canonical_key("kissan"), "kissa");
+        assert_eq!(v.canonical_key("Kissan"), "kissa");
+        assert_eq!(v.canonical_key("XYZZY"), "xyzzy");
+        assert_eq!(v.canonical_key("?!"), "?!");
+    }
+
+    #[test]
+    fn test_spell_into_result() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert_eq!(v.spell("kuningas").into_result(), Ok(()));
+        assert_eq!(v.spell("kuninggas").into_result(), Err(SpellError::Misspelled));
+        assert_eq!(
+            SpellReturn::CharsetConversionFailed.into_result(),
+            Err(SpellError::CharsetConversionFailed)
+        );
+        assert_eq!(format!("{}", SpellError::Misspelled), "incorrect spelling");
+    }
+
+    #[test]
+    fn test_tokens_by_sentence() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = "Järvenpää kuuluu Uudenmaan maakuntaan. Sen naapurikunnat ovat Mäntsälä koillisessa, \
+                    Sipoo idässä ja Tuusula etelässä, lännessä sekä pohjoisessa.";
+        let groups = v.tokens_by_sentence(text);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0][0], Token::new("Järvenpää", TokenType::Word));
+        assert_eq!(groups[0].last(), Some(&Token::new(" ", TokenType::Whitespace)));
+        assert_eq!(groups[1][0], Token::new("Sen", TokenType::Word));
+        assert_eq!(groups[1].last(), Some(&Token::new(".", TokenType::Punctuation)));
+        let flat: Vec<Token> = groups.into_iter().flatten().collect();
+        assert_eq!(flat, v.tokens(text));
+        assert!(v.tokens_by_sentence("").is_empty());
+    }
+
+    #[test]
+    fn test_analyze_first_cstr() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let word = std::ffi::CString::new("kaljakori").unwrap();
+        assert_eq!(v.analyze_first_cstr(&word, None), v.analyze_first("kaljakori"));
+        let analysis = v.analyze_first_cstr(&word, Some(&["BASEFORM"])).unwrap();
+        assert_eq!(analysis.len(), 1);
+        assert_eq!(analysis.baseform(), Some("kaljakori"));
+        assert_eq!(v.analyze_cstr(&word, None), v.analyze("kaljakori"));
+        // baseforms, word_classes, canonical_key and hyphenate_compound_parts convert the word
+        // to a C string only once
+        assert_eq!(v.baseforms("kaljakori"), vec!["kaljakori"]);
+        assert_eq!(v.word_classes("kaljakori"), vec![WordClass::Noun]);
+        assert_eq!(v.canonical_key("kaljakori"), "kaljakori");
+        assert_eq!(v.hyphenate_compound_parts("kaljakori", "-").unwrap(), vec!["kal-ja", "ko-ri"]);
+        assert_eq!(v.hyphenate_compound_parts("kissa", "-").unwrap(), vec!["kis-sa"]);
+        assert_eq!(v.analyze_first("kalja\0kori"), None);
+        assert!(v.baseforms("kalja\0kori").is_empty());
+        assert!(v.word_classes("kalja\0kori").is_empty());
+    }
+
+    #[test]
+    fn test_is_probable_fragment() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        v.set_opt_accept_titles_in_gc(true);
+        assert!(v.is_probable_fragment("Kissa nukkuu"));
+        assert!(!v.is_probable_fragment("Kissa nukkuu."));
+        assert!(v.options().accept_titles_in_gc);
+        assert!(!v.options().accept_unfinished_paragraphs_in_gc);
+    }
+
+    #[test]
+    fn test_tokens_checked() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = "Kissa ja koira, 42 kertaa!";
+        let (tokens, consumed) = v.tokens_checked(text);
+        assert_eq!(consumed, text.len());
+        assert_eq!(tokens, v.tokens(text));
+        assert_eq!(v.tokens_checked("kis\0sa"), (vec![], 0));
+        v.set_normalize_apostrophes(true);
+        let text = "Se on kahvi\u{2019}n hinta.";
+        assert_eq!(v.tokens_checked(text).1, text.len());
+    }
+
+    #[test]
+    fn test_gc_filtered() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = "Johanneksen leipäpuu pitää pitää leivottu juureen";
+        let errors = v.grammar_errors_filtered(text, "en", &[8]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, 9);
+        assert_eq!(v.grammar_errors_filtered(text, "en", &[]), v.grammar_errors(text, "en"));
+        assert!(v.grammar_errors_filtered(text, "en", &[8, 9]).is_empty());
+    }
+
+    #[test]
+    fn test_longest_baseform() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let baseforms = v.baseforms("kuusi");
+        assert!(baseforms.contains(&"kuu".to_string()));
+        assert_eq!(v.longest_baseform("kuusi"), Some("kuusi".to_string()));
+        assert_eq!(v.longest_baseform("kaljakori"), Some("kaljakori".to_string()));
+        assert_eq!(v.longest_baseform("kuninggas"), None);
+    }
+
+    #[test]
+    fn test_same_baseform() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        assert!(v.same_baseform("kissa", "kissan"));
+        assert!(v.same_baseform("kissan", "kissa"));
+        assert!(!v.same_baseform("kissa", "koira"));
+        assert!(!v.same_baseform("kuninggas", "kuninggas"));
+    }
+
+    #[test]
+    fn test_break_to_fit() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let word = "suihkumoottorimekaanikko";
+        let (head, tail) = v.break_to_fit(word, 10).unwrap().unwrap();
+        assert_eq!((head.as_str(), tail.as_str()), ("suihku", "moottorimekaanikko"));
+        assert!(head.chars().count() < 10);
+        assert_eq!(
+            v.break_to_fit(word, 11).unwrap(),
+            Some(("suihkumoot".to_string(), "torimekaanikko".to_string()))
+        );
+        assert_eq!(v.break_to_fit(word, 4).unwrap(), None);
+        assert_eq!(
+            v.break_to_fit("rei'ittää", 5).unwrap(),
+            Some(("rei".to_string(), "ittää".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_sentence_refs() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let text = "Yöllä sää on hyvä. Pääskyt lähtevät etelään.";
+        let sentences = v.sentence_refs(text);
+        assert_eq!(
+            sentences,
+            vec![
+                SentenceRef::new("Yöllä sää on hyvä. ", SentenceType::Probable),
+                SentenceRef::new("Pääskyt lähtevät etelään.", SentenceType::None),
+            ]
+        );
+        let text_range = text.as_bytes().as_ptr_range();
+        for sentence in &sentences {
+            assert!(text_range.contains(&sentence.text.as_ptr()));
+        }
+        assert_eq!(sentences[1].text.as_ptr(), text[sentences[0].text.len()..].as_ptr());
+        let owned: Vec<Sentence> = sentences.into_iter().map(Sentence::from).collect();
+        assert_eq!(owned, v.sentences(text));
+    }
+
+    #[test]
+    fn test_available_analysis_keys() {
+        let v = Voikko::new("fi-x-morphoid", None).unwrap();
+        let keys = v.available_analysis_keys("kissa");
+        assert!(keys.contains(&"BASEFORM".to_string()));
+        assert!(keys.contains(&"CLASS".to_string()));
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(v.available_analysis_keys("kuninggas").is_empty());
+    }
 }